@@ -0,0 +1,64 @@
+//! Generates a test per part of every example in `examples/dayNN/`, see
+//! src/examples/spec.rs for their format.
+
+use std::{fmt::Write, fs, path::PathBuf};
+
+#[path = "src/examples/spec.rs"]
+mod spec;
+
+/// The examples of each day, by day and then name.
+fn examples() -> Vec<(usize, PathBuf)> {
+    let mut examples = vec![];
+    for dir in fs::read_dir("examples").into_iter().flatten().flatten() {
+        let name = dir.file_name();
+        let Some(day) = name.to_str().and_then(|n| n.strip_prefix("day")) else {
+            continue;
+        };
+        let Ok(day) = day.parse() else {
+            continue;
+        };
+        for file in fs::read_dir(dir.path()).into_iter().flatten().flatten() {
+            let path = file.path();
+            if path.extension().is_some_and(|e| e == "txt") {
+                examples.push((day, path));
+            }
+        }
+    }
+    examples.sort();
+    examples
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed=examples");
+    println!("cargo:rerun-if-changed=src/examples/spec.rs");
+
+    let mut tests = String::new();
+    for (day, path) in examples() {
+        // Cleaned up the way input::normalize does for the tests
+        let text = fs::read_to_string(&path).unwrap_or_default();
+        let text = text.trim_start_matches('\u{feff}').replace("\r\n", "\n");
+        let sidecar = fs::read_to_string(path.with_extension("toml")).ok();
+        // Broken examples get a test anyway, which fails with the reason
+        let header = spec::split(&text, sidecar.as_deref()).map(|(header, _)| header);
+
+        let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+        let stem: String = stem
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+        let path = path.to_string_lossy().replace('\\', "/");
+        for part in [1, 2] {
+            if header
+                .as_ref()
+                .is_ok_and(|h| !h.contains_key(&spec::answer_key(part)))
+            {
+                writeln!(tests, "#[ignore = \"missing expected answer\"]").unwrap();
+            }
+            writeln!(tests, "#[test]\nfn day{day:02}_{stem}_part{part}() {{").unwrap();
+            writeln!(tests, "    check({day}, {part}, {path:?});\n}}\n").unwrap();
+        }
+    }
+
+    let out = PathBuf::from(std::env::var_os("OUT_DIR").expect("OUT_DIR is set by cargo"));
+    fs::write(out.join("examples.rs"), tests).expect("can write to OUT_DIR");
+}
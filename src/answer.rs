@@ -0,0 +1,71 @@
+/// A puzzle answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Int(i128),
+    Big(num::BigInt),
+    Str(String),
+}
+
+impl std::fmt::Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Int(v) => write!(f, "{v}"),
+            Answer::Big(v) => write!(f, "{v}"),
+            Answer::Str(v) => write!(f, "{v}"),
+        }
+    }
+}
+
+macro_rules! from_int {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(value: $t) -> Self {
+                    Answer::Int(value as i128)
+                }
+            }
+        )*
+    };
+}
+
+from_int!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, i128);
+
+impl From<num::BigInt> for Answer {
+    fn from(value: num::BigInt) -> Self {
+        Answer::Big(value)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Str(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Answer::Str(value.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Answer;
+
+    #[test]
+    fn answer_from() {
+        assert_eq!(Answer::from(142u32), Answer::Int(142));
+        assert_eq!(Answer::from(-3isize), Answer::Int(-3));
+        assert_eq!(Answer::from(usize::MAX), Answer::Int(usize::MAX as i128));
+        assert_eq!(Answer::from("abc"), Answer::Str("abc".to_string()));
+    }
+
+    #[test]
+    fn answer_display() {
+        assert_eq!(Answer::from(-3i64).to_string(), "-3");
+        assert_eq!(Answer::from(u64::MAX).to_string(), "18446744073709551615");
+        let big: num::BigInt = num::BigInt::from(u64::MAX) * 1000;
+        assert_eq!(Answer::from(big).to_string(), "18446744073709551615000");
+        assert_eq!(Answer::from("xyz".to_string()).to_string(), "xyz");
+    }
+}
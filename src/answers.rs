@@ -0,0 +1,129 @@
+use std::{collections::BTreeMap, path::Path};
+
+use crate::{
+    runner::{self, Status},
+    solver::Solver,
+};
+
+#[derive(Debug, Default, serde::Deserialize)]
+struct DayAnswers {
+    part1: Option<String>,
+    part2: Option<String>,
+}
+
+/// Accepted answers, keyed by `dayN` tables with `part1`/`part2` entries.
+#[derive(Debug, Default, serde::Deserialize)]
+#[serde(transparent)]
+pub struct Answers(BTreeMap<String, DayAnswers>);
+
+impl Answers {
+    pub fn parse(s: &str) -> anyhow::Result<Self> {
+        Ok(toml::from_str(s)?)
+    }
+
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let s = std::fs::read_to_string(path)
+            .map_err(|e| anyhow::anyhow!("{}: {e}", path.display()))?;
+        Self::parse(&s)
+    }
+
+    pub fn get(&self, day: usize, part: u8) -> Option<&str> {
+        let day = self.0.get(&format!("day{day}"))?;
+        match part {
+            1 => day.part1.as_deref(),
+            _ => day.part2.as_deref(),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Pass,
+    Mismatch {
+        expected: String,
+        actual: String,
+    },
+    Failed(Status),
+    /// No accepted answer recorded, so the part isn't run
+    Unknown,
+}
+
+pub fn verify(solver: &dyn Solver, part: u8, input: &str, answers: &Answers) -> Outcome {
+    let Some(expected) = answers.get(solver.day(), part) else {
+        return Outcome::Unknown;
+    };
+    let res = runner::run_part(solver, part, input);
+    match res.answer {
+        Some(actual) if actual == expected => Outcome::Pass,
+        Some(actual) => Outcome::Mismatch {
+            expected: expected.to_string(),
+            actual,
+        },
+        None => Outcome::Failed(res.status),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Answers, Outcome};
+
+    struct Fake;
+
+    impl crate::solver::Solver for Fake {
+        fn day(&self) -> usize {
+            3
+        }
+
+        fn title(&self) -> &'static str {
+            "Fake"
+        }
+
+        fn part1(&self, input: &str) -> anyhow::Result<crate::answer::Answer> {
+            Ok(input.len().into())
+        }
+
+        fn part2(&self, _input: &str) -> anyhow::Result<crate::answer::Answer> {
+            anyhow::bail!("broken")
+        }
+    }
+
+    const ANSWERS: &str = r#"
+[day3]
+part1 = "4"
+part2 = "7"
+
+[day4]
+part1 = "1"
+"#;
+
+    #[test]
+    fn answers_get() {
+        let answers = Answers::parse(ANSWERS).unwrap();
+        assert_eq!(answers.get(3, 1), Some("4"));
+        assert_eq!(answers.get(3, 2), Some("7"));
+        assert_eq!(answers.get(4, 2), None);
+        assert_eq!(answers.get(5, 1), None);
+        assert!(Answers::parse("[day1]\npart1 = 12").is_err());
+    }
+
+    #[test]
+    fn answers_verify() {
+        let answers = Answers::parse(ANSWERS).unwrap();
+        assert_eq!(super::verify(&Fake, 1, "abcd", &answers), Outcome::Pass);
+        assert_eq!(
+            super::verify(&Fake, 1, "abc", &answers),
+            Outcome::Mismatch {
+                expected: "4".to_string(),
+                actual: "3".to_string()
+            }
+        );
+        assert!(matches!(
+            super::verify(&Fake, 2, "abc", &answers),
+            Outcome::Failed(_)
+        ));
+        assert_eq!(
+            super::verify(&Fake, 1, "abcd", &Answers::default()),
+            Outcome::Unknown
+        );
+    }
+}
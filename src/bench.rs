@@ -0,0 +1,158 @@
+use std::{path::Path, time::Duration};
+
+use crate::{
+    runner::{self, Status},
+    solver::Solver,
+};
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Timing {
+    pub day: usize,
+    pub part: u8,
+    pub min_ns: u64,
+    pub median_ns: u64,
+    pub max_ns: u64,
+}
+
+impl Timing {
+    fn from_samples(day: usize, part: u8, samples: &mut [Duration]) -> Self {
+        samples.sort();
+        let ns = |d: Duration| d.as_nanos() as u64;
+        Self {
+            day,
+            part,
+            min_ns: ns(samples[0]),
+            median_ns: ns(samples[samples.len() / 2]),
+            max_ns: ns(samples[samples.len() - 1]),
+        }
+    }
+
+    /// Change of the median relative to `baseline`, in percent.
+    pub fn change(&self, baseline: &Timing) -> f64 {
+        (self.median_ns as f64 - baseline.median_ns as f64) / baseline.median_ns.max(1) as f64
+            * 100.0
+    }
+}
+
+/// Times one part, giving up on the first run that doesn't succeed.
+pub fn measure(
+    solver: &dyn Solver,
+    part: u8,
+    input: &str,
+    warmup: u32,
+    iterations: u32,
+) -> Result<Timing, Status> {
+    let mut samples = Vec::with_capacity(iterations as usize);
+    for i in 0..(warmup + iterations) {
+        let res = runner::run_part(solver, part, input);
+        if res.status != Status::Ok {
+            return Err(res.status);
+        }
+        if i >= warmup {
+            samples.push(res.elapsed);
+        }
+    }
+    Ok(Timing::from_samples(solver.day(), part, &mut samples))
+}
+
+pub fn load(path: &Path) -> anyhow::Result<Vec<Timing>> {
+    let s = std::fs::read_to_string(path)?;
+    Ok(serde_json::from_str(&s)?)
+}
+
+pub fn save(path: &Path, timings: &[Timing]) -> anyhow::Result<()> {
+    std::fs::write(path, serde_json::to_string_pretty(timings)?)?;
+    Ok(())
+}
+
+/// Timings whose median is more than `threshold` percent slower than the
+/// matching baseline entry.
+pub fn regressions<'a>(
+    timings: &'a [Timing],
+    baseline: &[Timing],
+    threshold: f64,
+) -> Vec<(&'a Timing, f64)> {
+    timings
+        .iter()
+        .filter_map(|t| {
+            let b = baseline
+                .iter()
+                .find(|b| b.day == t.day && b.part == t.part)?;
+            let change = t.change(b);
+            (change > threshold).then_some((t, change))
+        })
+        .collect()
+}
+
+fn fmt_ns(ns: u64) -> String {
+    format!("{:.2?}", Duration::from_nanos(ns))
+}
+
+pub fn print_table(timings: &[Timing], baseline: &[Timing], threshold: f64) {
+    println!(
+        "{:>3}  {:>4}  {:>10}  {:>10}  {:>10}  {:>9}",
+        "Day", "Part", "Min", "Median", "Max", "Change"
+    );
+    for t in timings {
+        let change = baseline
+            .iter()
+            .find(|b| b.day == t.day && b.part == t.part)
+            .map(|b| t.change(b));
+        println!(
+            "{:>3}  {:>4}  {:>10}  {:>10}  {:>10}  {:>9}{}",
+            t.day,
+            t.part,
+            fmt_ns(t.min_ns),
+            fmt_ns(t.median_ns),
+            fmt_ns(t.max_ns),
+            change.map_or("-".to_string(), |c| format!("{c:+.1}%")),
+            if change.is_some_and(|c| c > threshold) {
+                "  REGRESSION"
+            } else {
+                ""
+            }
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::Timing;
+
+    fn timing(day: usize, median_ns: u64) -> Timing {
+        Timing {
+            day,
+            part: 1,
+            min_ns: median_ns,
+            median_ns,
+            max_ns: median_ns,
+        }
+    }
+
+    #[test]
+    fn bench_from_samples() {
+        let mut samples: Vec<_> = [5, 1, 3, 4, 2].map(Duration::from_nanos).to_vec();
+        let t = Timing::from_samples(3, 2, &mut samples);
+        assert_eq!((t.min_ns, t.median_ns, t.max_ns), (1, 3, 5));
+    }
+
+    #[test]
+    fn bench_regressions() {
+        let baseline = [timing(1, 100), timing(2, 100)];
+        let timings = [timing(1, 105), timing(2, 150), timing(3, 1000)];
+        let r = super::regressions(&timings, &baseline, 10.0);
+        assert_eq!(r.len(), 1);
+        assert_eq!(r[0].0.day, 2);
+        assert!((r[0].1 - 50.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn bench_json_roundtrip() {
+        let timings = vec![timing(1, 100), timing(2, 200)];
+        let s = serde_json::to_string(&timings).unwrap();
+        let back: Vec<Timing> = serde_json::from_str(&s).unwrap();
+        assert_eq!(back, timings);
+    }
+}
@@ -0,0 +1,228 @@
+use std::path::PathBuf;
+
+use crate::solver::{self, Solver};
+
+#[derive(Debug, clap::Parser)]
+#[command(about = "Advent of Code 2023 solutions")]
+pub struct Cli {
+    /// Puzzle year
+    #[arg(long, global = true, default_value_t = 2023)]
+    pub year: usize,
+
+    /// Directory holding the dayNN.txt inputs [default: $AOC_INPUT_DIR, then inputs/<year>]
+    #[arg(long, global = true)]
+    pub input_dir: Option<PathBuf>,
+
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum Command {
+    /// Run one day, or every day with --all
+    Run(RunArgs),
+    /// Time each part over repeated runs
+    Bench(BenchArgs),
+    /// Check the solvers against the accepted answers
+    Verify(VerifyArgs),
+    /// Check the puzzle inputs against what the solvers assume about them
+    CheckInput(CheckInputArgs),
+    /// List the available days
+    List,
+}
+
+/// Which days and parts a command applies to.
+#[derive(Debug, clap::Args)]
+pub struct Select {
+    /// Day to run
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25),
+          required_unless_present = "all", conflicts_with = "all")]
+    pub day: Option<u8>,
+
+    /// Only run this part
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    pub part: Option<u8>,
+
+    /// Run every day
+    #[arg(long)]
+    pub all: bool,
+}
+
+impl Select {
+    pub fn runs_part(&self, part: u8) -> bool {
+        self.part.is_none_or(|p| p == part)
+    }
+
+    pub fn parts(&self) -> Vec<u8> {
+        [1, 2].into_iter().filter(|p| self.runs_part(*p)).collect()
+    }
+
+    pub fn solvers(&self) -> anyhow::Result<Vec<&'static dyn Solver>> {
+        match self.day {
+            Some(day) if !self.all => Ok(vec![
+                solver::get(day as usize).ok_or(anyhow::anyhow!("No such day"))?
+            ]),
+            _ => Ok(solver::all().to_vec()),
+        }
+    }
+}
+
+#[derive(Debug, clap::Args)]
+pub struct RunArgs {
+    #[command(flatten)]
+    pub select: Select,
+
+    /// Read the puzzle input from this file, or from stdin if "-"
+    #[arg(long, conflicts_with = "all")]
+    pub input: Option<PathBuf>,
+
+    /// Output format; json prints one object per part
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    pub format: Format,
+
+    /// With --all, give up on a part after this many seconds
+    #[arg(long, conflicts_with = "day", value_parser = clap::value_parser!(u64).range(1..))]
+    pub timeout: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Format {
+    Text,
+    Json,
+}
+
+#[derive(Debug, clap::Args)]
+pub struct BenchArgs {
+    #[command(flatten)]
+    pub select: Select,
+
+    /// Timed runs per part
+    #[arg(long, default_value_t = 10, value_parser = clap::value_parser!(u32).range(1..))]
+    pub iterations: u32,
+
+    /// Untimed runs per part before measuring
+    #[arg(long, default_value_t = 1)]
+    pub warmup: u32,
+
+    /// Write the results to this JSON file
+    #[arg(long)]
+    pub save: Option<PathBuf>,
+
+    /// Compare against a JSON file written by an earlier --save
+    #[arg(long)]
+    pub baseline: Option<PathBuf>,
+
+    /// Median slowdown, in percent, that counts as a regression
+    #[arg(long, default_value_t = 10.0)]
+    pub threshold: f64,
+}
+
+#[derive(Debug, clap::Args)]
+pub struct VerifyArgs {
+    /// Only verify this day
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+    pub day: Option<u8>,
+
+    /// File with the accepted answers
+    #[arg(long, default_value = "answers.toml")]
+    pub answers: PathBuf,
+}
+
+#[derive(Debug, clap::Args)]
+pub struct CheckInputArgs {
+    /// Only check this day
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+    pub day: Option<u8>,
+
+    /// Check this file instead of the stored input, or stdin if "-"
+    #[arg(long, requires = "day")]
+    pub input: Option<PathBuf>,
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+
+    use super::{Cli, Command, Format};
+
+    #[test]
+    fn cli_run() {
+        let cli = Cli::try_parse_from(["aoc", "run", "--day", "5", "--part", "2"]).unwrap();
+        assert_eq!(cli.year, 2023);
+        let Command::Run(args) = cli.command else {
+            panic!("expected run");
+        };
+        assert_eq!(args.select.day, Some(5));
+        assert_eq!(args.select.parts(), vec![2]);
+        assert_eq!(args.format, Format::Text);
+        assert_eq!(args.select.solvers().unwrap()[0].day(), 5);
+
+        let cli =
+            Cli::try_parse_from(["aoc", "--year", "2022", "run", "--all", "--format", "json"])
+                .unwrap();
+        assert_eq!(cli.year, 2022);
+        let Command::Run(args) = cli.command else {
+            panic!("expected run");
+        };
+        assert_eq!(args.select.parts(), vec![1, 2]);
+        assert_eq!(args.select.solvers().unwrap().len(), 25);
+        assert_eq!(args.format, Format::Json);
+        assert_eq!(args.timeout, None);
+
+        let cli = Cli::try_parse_from(["aoc", "run", "--all", "--timeout", "30"]).unwrap();
+        let Command::Run(args) = cli.command else {
+            panic!("expected run");
+        };
+        assert_eq!(args.timeout, Some(30));
+        assert!(Cli::try_parse_from(["aoc", "run", "--day", "1", "--timeout", "30"]).is_err());
+        assert!(Cli::try_parse_from(["aoc", "run", "--all", "--timeout", "0"]).is_err());
+    }
+
+    #[test]
+    fn cli_bench() {
+        let cli = Cli::try_parse_from(["aoc", "bench", "--all", "--save", "b.json"]).unwrap();
+        let Command::Bench(args) = cli.command else {
+            panic!("expected bench");
+        };
+        assert_eq!(args.iterations, 10);
+        assert_eq!(args.warmup, 1);
+        assert_eq!(args.save.unwrap().to_str(), Some("b.json"));
+        assert!(args.baseline.is_none());
+    }
+
+    #[test]
+    fn cli_verify() {
+        let cli = Cli::try_parse_from(["aoc", "verify", "--input-dir", "x"]).unwrap();
+        assert_eq!(cli.input_dir.unwrap().to_str(), Some("x"));
+        let Command::Verify(args) = cli.command else {
+            panic!("expected verify");
+        };
+        assert_eq!(args.day, None);
+        assert_eq!(args.answers.to_str(), Some("answers.toml"));
+    }
+
+    #[test]
+    fn cli_check_input() {
+        let cli =
+            Cli::try_parse_from(["aoc", "check-input", "--day", "10", "--input", "-"]).unwrap();
+        let Command::CheckInput(args) = cli.command else {
+            panic!("expected check-input");
+        };
+        assert_eq!(args.day, Some(10));
+        assert_eq!(args.input.unwrap().to_str(), Some("-"));
+        assert!(Cli::try_parse_from(["aoc", "check-input", "--input", "x"]).is_err());
+    }
+
+    #[test]
+    fn cli_invalid() {
+        assert!(Cli::try_parse_from(["aoc"]).is_err());
+        assert!(Cli::try_parse_from(["aoc", "run"]).is_err());
+        assert!(Cli::try_parse_from(["aoc", "run", "--day", "0"]).is_err());
+        assert!(Cli::try_parse_from(["aoc", "run", "--day", "26"]).is_err());
+        assert!(Cli::try_parse_from(["aoc", "run", "--day", "x"]).is_err());
+        assert!(Cli::try_parse_from(["aoc", "run", "--day", "1", "--part", "3"]).is_err());
+        assert!(Cli::try_parse_from(["aoc", "run", "--day", "1", "--all"]).is_err());
+        assert!(Cli::try_parse_from(["aoc", "run", "--day", "1", "--format", "xml"]).is_err());
+        assert!(Cli::try_parse_from(["aoc", "bench", "--day", "1", "--iterations", "0"]).is_err());
+    }
+}
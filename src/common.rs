@@ -1,61 +1,130 @@
-#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq)]
-pub struct Coord(pub isize, pub isize);
+pub mod cycle;
+pub mod geometry;
+pub mod graph;
+mod grid;
+pub mod intervals;
+pub mod math;
+pub mod parse;
+mod point;
+pub mod validate;
 
-impl std::ops::Add<Coord> for Coord {
-    type Output = Coord;
+pub use grid::Grid;
+pub use point::Coord3;
 
-    fn add(self, rhs: Coord) -> Self::Output {
-        (self.0 + rhs.0, self.1 + rhs.1).into()
+/// A grid position as (row, col).
+pub type Coord = point::Coord2<isize>;
+
+impl Coord {
+    pub fn irow(&self) -> isize {
+        self.0
     }
-}
 
-impl std::ops::Sub<Coord> for Coord {
-    type Output = Coord;
+    pub fn icol(&self) -> isize {
+        self.1
+    }
 
-    fn sub(self, rhs: Coord) -> Self::Output {
-        (self.0 - rhs.0, self.1 - rhs.1).into()
+    /// The (row, col) indices, or `None` if either is negative.
+    pub fn index(&self) -> Option<(usize, usize)> {
+        (*self).try_into().ok()
     }
-}
 
-impl std::fmt::Display for Coord {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "({}, {})", self.0, self.1)
+    /// The cells of `neighborhood` around this one, leaving out those
+    /// outside `bounds` if given.
+    pub fn around<'a>(
+        &self,
+        neighborhood: Neighborhood<'a>,
+        bounds: Option<Bounds>,
+    ) -> impl Iterator<Item = Self> + 'a {
+        let c = *self;
+        neighborhood
+            .offsets(c, bounds)
+            .map(move |d| c + d)
+            .filter(move |n| bounds.is_none_or(|b| b.contains(*n)))
+    }
+
+    pub fn manhattan(&self, other: &Self) -> usize {
+        (*self - *other).manhattan_norm() as usize
     }
 }
 
+/// Offsets to the orthogonal neighbours: N, S, W, E.
+pub const ORTHOGONAL: [Coord; 4] = [
+    Coord::new(-1, 0),
+    Coord::new(1, 0),
+    Coord::new(0, -1),
+    Coord::new(0, 1),
+];
 
-impl Coord {
-    pub fn new(row: isize, col: isize) -> Self {
-        (row, col).into()
-    }
-    pub fn row(&self) -> usize {
-        self.0 as _
-    }
+/// Offsets to all eight neighbours, clockwise from north like [`Dir::ALL`].
+pub const SURROUNDING: [Coord; 8] = [
+    Coord::new(-1, 0),
+    Coord::new(-1, 1),
+    Coord::new(0, 1),
+    Coord::new(1, 1),
+    Coord::new(1, 0),
+    Coord::new(1, -1),
+    Coord::new(0, -1),
+    Coord::new(-1, -1),
+];
 
-    pub fn irow(&self) -> isize {
-        self.0
-    }
+/// Which cells count as neighbours. The centre cell is never included.
+#[derive(Clone, Copy, Debug)]
+pub enum Neighborhood<'a> {
+    Orthogonal,
+    Surrounding,
+    /// A custom stencil of offsets from the centre
+    Offsets(&'a [Coord]),
+    /// Every cell within this Manhattan distance
+    Manhattan(usize),
+}
 
-    pub fn col(&self) -> usize {
-        self.1 as _
+impl<'a> Neighborhood<'a> {
+    /// The offsets from `centre`. The fixed neighbourhoods are slices, only
+    /// the diamond of `Manhattan` is generated, and only the rows and columns
+    /// of it inside `bounds`.
+    fn offsets(self, centre: Coord, bounds: Option<Bounds>) -> impl Iterator<Item = Coord> + 'a {
+        let (fixed, r): (&[Coord], isize) = match self {
+            Neighborhood::Orthogonal => (&ORTHOGONAL, 0),
+            Neighborhood::Surrounding => (&SURROUNDING, 0),
+            Neighborhood::Offsets(offsets) => (offsets, 0),
+            Neighborhood::Manhattan(r) => (&[], r.try_into().unwrap_or(isize::MAX)),
+        };
+        let (min, max) = match bounds {
+            Some(b) => (b.min - centre, b.max - centre),
+            None => (Coord::new(-r, -r), Coord::new(r, r)),
+        };
+        let diamond = ((-r).max(min.0)..=r.min(max.0))
+            .flat_map(move |dr| {
+                let w = r - dr.abs();
+                ((-w).max(min.1)..=w.min(max.1)).map(move |dc| Coord::new(dr, dc))
+            })
+            .filter(|d| *d != Coord::new(0, 0));
+        fixed.iter().copied().chain(diamond)
     }
+}
 
-    pub fn icol(&self) -> isize {
-        self.1
+/// An inclusive rectangle of coordinates.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Bounds {
+    pub min: Coord,
+    pub max: Coord,
+}
+
+impl Bounds {
+    pub fn new(min: Coord, max: Coord) -> Self {
+        Self { min, max }
     }
 
-    pub fn neighbors(&self) -> impl Iterator<Item = Self> {
-        [
-            (self.irow() - 1, self.icol()).into(),
-            (self.irow() + 1, self.icol()).into(),
-            (self.irow(), self.icol() - 1).into(),
-            (self.irow(), self.icol() + 1).into(),
-        ]
-        .into_iter()
+    pub fn contains(&self, c: Coord) -> bool {
+        (self.min.0..=self.max.0).contains(&c.0) && (self.min.1..=self.max.1).contains(&c.1)
     }
+}
 
-    pub fn manhattan(&self, other: &Self) -> usize {
-        ((self.irow() - other.irow()).abs() + (self.icol() - other.icol()).abs()) as usize
+impl TryFrom<Coord> for (usize, usize) {
+    type Error = std::num::TryFromIntError;
+
+    fn try_from(c: Coord) -> Result<Self, Self::Error> {
+        Ok((c.0.try_into()?, c.1.try_into()?))
     }
 }
 
@@ -77,42 +146,124 @@ impl From<(i32, i32)> for Coord {
     }
 }
 
+/// A compass direction, in clockwise order from north. North is up, i.e.
+/// towards lower rows.
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
 pub(crate) enum Dir {
     N,
-    S,
+    NE,
     E,
+    SE,
+    S,
+    SW,
     W,
+    NW,
 }
 
 impl Dir {
+    /// All directions, clockwise from north.
+    pub(crate) const ALL: [Dir; 8] = [
+        Dir::N,
+        Dir::NE,
+        Dir::E,
+        Dir::SE,
+        Dir::S,
+        Dir::SW,
+        Dir::W,
+        Dir::NW,
+    ];
+
+    /// The four orthogonal directions, clockwise from north.
+    pub(crate) const CARDINAL: [Dir; 4] = [Dir::N, Dir::E, Dir::S, Dir::W];
+
+    /// Turns clockwise by `degrees`, which must be a multiple of 45.
+    /// Negative angles turn anticlockwise.
+    pub(crate) fn turn(self, degrees: i32) -> Self {
+        assert!(degrees % 45 == 0, "Can't turn by {degrees} degrees");
+        Self::ALL[(self as i32 + degrees / 45).rem_euclid(8) as usize]
+    }
+
     pub(crate) fn turn_left(self) -> Self {
-        match self {
-            Dir::N => Self::W,
-            Dir::S => Self::E,
-            Dir::E => Self::N,
-            Dir::W => Self::S,
-        }
+        self.turn(-90)
     }
 
     pub(crate) fn turn_right(self) -> Self {
-        match self {
-            Dir::N => Self::E,
-            Dir::S => Self::W,
-            Dir::E => Self::S,
-            Dir::W => Self::N,
-        }
+        self.turn(90)
+    }
+
+    pub(crate) fn reverse(self) -> Self {
+        self.turn(180)
     }
 
     pub(crate) fn movement(&self) -> Coord {
         match self {
             Dir::N => (-1, 0),
-            Dir::S => (1, 0),
+            Dir::NE => (-1, 1),
             Dir::E => (0, 1),
+            Dir::SE => (1, 1),
+            Dir::S => (1, 0),
+            Dir::SW => (1, -1),
             Dir::W => (0, -1),
+            Dir::NW => (-1, -1),
         }
         .into()
     }
+
+    /// Parses the compass points `N`, `NE`, `E`, ... `NW`.
+    pub(crate) fn from_compass(s: &str) -> Option<Self> {
+        match s {
+            "N" => Some(Dir::N),
+            "NE" => Some(Dir::NE),
+            "E" => Some(Dir::E),
+            "SE" => Some(Dir::SE),
+            "S" => Some(Dir::S),
+            "SW" => Some(Dir::SW),
+            "W" => Some(Dir::W),
+            "NW" => Some(Dir::NW),
+            _ => None,
+        }
+    }
+
+    /// Parses the arrows `^>v<` of slope and conveyor tiles.
+    pub(crate) fn from_arrow(c: char) -> Option<Self> {
+        match c {
+            '^' => Some(Dir::N),
+            '>' => Some(Dir::E),
+            'v' => Some(Dir::S),
+            '<' => Some(Dir::W),
+            _ => None,
+        }
+    }
+
+    /// Parses the up, down, left and right of `UDLR` instructions.
+    pub(crate) fn from_udlr(c: char) -> Option<Self> {
+        match c {
+            'U' => Some(Dir::N),
+            'R' => Some(Dir::E),
+            'D' => Some(Dir::S),
+            'L' => Some(Dir::W),
+            _ => None,
+        }
+    }
+
+    /// Decodes 0 to 3 as right, down, left, up.
+    pub(crate) fn from_digit(d: u32) -> Option<Self> {
+        match d {
+            0 => Some(Dir::E),
+            1 => Some(Dir::S),
+            2 => Some(Dir::W),
+            3 => Some(Dir::N),
+            _ => None,
+        }
+    }
+}
+
+impl std::str::FromStr for Dir {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_compass(s).ok_or_else(|| format!("Invalid direction {s:?}"))
+    }
 }
 
 #[derive(Clone, Debug, Copy, PartialEq, Eq, Hash)]
@@ -146,12 +297,135 @@ impl Pos {
             dir: self.dir.turn_left(),
         }
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Bounds, Coord, Dir, Neighborhood, SURROUNDING};
+
+    #[test]
+    fn coord_around() {
+        let c = Coord::new(5, 5);
+        let orth: Vec<Coord> = c.around(Neighborhood::Orthogonal, None).collect();
+        assert_eq!(orth, [(4, 5), (6, 5), (5, 4), (5, 6)].map(Coord::from));
+        assert_eq!(c.around(Neighborhood::Surrounding, None).count(), 8);
+        assert_eq!(SURROUNDING, Dir::ALL.map(|d| d.movement()));
+        assert!(c
+            .around(Neighborhood::Surrounding, None)
+            .all(|n| n != c && (n.0 - c.0).abs() <= 1 && (n.1 - c.1).abs() <= 1));
+
+        let knight = [Coord::new(-2, 1), Coord::new(1, 2)];
+        let moves: Vec<Coord> = c.around(Neighborhood::Offsets(&knight), None).collect();
+        assert_eq!(moves, [Coord::new(3, 6), Coord::new(6, 7)]);
+
+        assert_eq!(c.around(Neighborhood::Manhattan(0), None).count(), 0);
+        assert_eq!(c.around(Neighborhood::Manhattan(1), None).count(), 4);
+        assert_eq!(c.around(Neighborhood::Manhattan(2), None).count(), 12);
+        assert!(c
+            .around(Neighborhood::Manhattan(3), None)
+            .all(|n| (1..=3).contains(&n.manhattan(&c))));
+    }
+
+    #[test]
+    fn coord_index() {
+        assert_eq!(Coord::new(0, 0).index(), Some((0, 0)));
+        assert_eq!(Coord::new(3, 7).index(), Some((3, 7)));
+        assert_eq!(Coord::new(-1, 0).index(), None);
+        assert_eq!(Coord::new(0, -1).index(), None);
+        assert_eq!(Coord::new(isize::MIN, 2).index(), None);
+        assert!(<(usize, usize)>::try_from(Coord::new(-1, 5)).is_err());
+        assert_eq!(<(usize, usize)>::try_from(Coord::new(2, 5)), Ok((2, 5)));
+        let back: Coord = Coord::new(4, 9).index().unwrap().into();
+        assert_eq!(back, Coord::new(4, 9));
+    }
+
+    #[test]
+    fn coord_around_bounds() {
+        let bounds = Bounds::new(Coord::new(0, 0), Coord::new(2, 2));
+        let corner = Coord::new(0, 0);
+        assert_eq!(
+            corner
+                .around(Neighborhood::Orthogonal, Some(bounds))
+                .count(),
+            2
+        );
+        assert_eq!(
+            corner
+                .around(Neighborhood::Surrounding, Some(bounds))
+                .count(),
+            3
+        );
+        assert_eq!(
+            corner
+                .around(Neighborhood::Manhattan(2), Some(bounds))
+                .count(),
+            5
+        );
+        assert!(corner
+            .around(Neighborhood::Surrounding, Some(bounds))
+            .all(|n| n.0 >= 0 && n.1 >= 0));
+        assert_eq!(
+            Coord::new(-5, 0)
+                .around(Neighborhood::Orthogonal, Some(bounds))
+                .count(),
+            0
+        );
+        // Only the part of the diamond inside the bounds is generated
+        let big = Neighborhood::Manhattan(usize::MAX);
+        assert_eq!(Coord::new(1, 1).around(big, Some(bounds)).count(), 8);
+        assert_eq!(corner.around(big, Some(bounds)).count(), 8);
+    }
+
+    #[test]
+    fn dir_turns() {
+        assert_eq!(Dir::N.turn_right(), Dir::E);
+        assert_eq!(Dir::N.turn_left(), Dir::W);
+        assert_eq!(Dir::W.turn_right(), Dir::N);
+        assert_eq!(Dir::N.turn(45), Dir::NE);
+        assert_eq!(Dir::N.turn(-135), Dir::SW);
+        assert_eq!(Dir::SE.turn(720), Dir::SE);
+        for d in Dir::ALL {
+            assert_eq!(d.reverse().reverse(), d);
+            assert_eq!(d.reverse().movement(), -d.movement());
+            assert_eq!(d.turn_left().turn_right(), d);
+        }
+        assert_eq!(
+            Dir::CARDINAL.map(|d| d.movement()),
+            [(-1, 0), (0, 1), (1, 0), (0, -1)].map(Coord::from)
+        );
+    }
 
-    pub fn row(&self) -> usize {
-        self.coord.row()
+    #[test]
+    #[should_panic(expected = "Can't turn by 30 degrees")]
+    fn dir_turn_odd_angle() {
+        Dir::N.turn(30);
     }
 
-    pub fn col(&self) -> usize {
-        self.coord.col()
+    #[test]
+    fn dir_parse() {
+        for (chars, d) in ["U^3", "R>0", "Dv1", "L<2"].into_iter().zip(Dir::CARDINAL) {
+            let chars: Vec<char> = chars.chars().collect();
+            assert_eq!(Dir::from_udlr(chars[0]), Some(d));
+            assert_eq!(Dir::from_arrow(chars[1]), Some(d));
+            assert_eq!(Dir::from_digit(chars[2].to_digit(10).unwrap()), Some(d));
+        }
+        for c in ['N', 'x', '3', '<'] {
+            assert_eq!(Dir::from_udlr(c), None);
+        }
+        for c in ['N', 'L', '3', 'V'] {
+            assert_eq!(Dir::from_arrow(c), None);
+        }
+        let points = ["N", "NE", "E", "SE", "S", "SW", "W", "NW"];
+        assert_eq!(points.map(Dir::from_compass), Dir::ALL.map(Some));
+        for s in ["", "n", "U", "NNE", "EN", "^"] {
+            assert_eq!(Dir::from_compass(s), None);
+        }
+        assert_eq!("SW".parse(), Ok(Dir::SW));
+        assert_eq!(
+            "x".parse::<Dir>(),
+            Err("Invalid direction \"x\"".to_string())
+        );
+        assert_eq!(Dir::from_digit(2), Some(Dir::W));
+        assert_eq!(Dir::from_digit(7), None);
     }
 }
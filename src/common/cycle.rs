@@ -0,0 +1,186 @@
+//! Cycle detection for iterated functions, to skip ahead in puzzles that ask
+//! for the state after a huge number of steps.
+
+use std::collections::HashMap;
+use std::hash::{BuildHasher, Hash, RandomState};
+
+/// The sequence `x0, f(x0), f(f(x0)), ...` repeats every `len` steps from
+/// index `start` on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub len: usize,
+}
+
+impl Cycle {
+    /// The first index with the same state as index `n`.
+    pub fn reduce(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.len
+        }
+    }
+}
+
+/// Floyd's tortoise and hare. Keeps two states at a time, at the cost of
+/// calling `f` about three times per state up to the end of the first cycle.
+pub fn floyd<T: PartialEq>(x0: T, mut f: impl FnMut(&T) -> T) -> Cycle {
+    let mut tortoise = f(&x0);
+    let mut hare = f(&tortoise);
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        let next = f(&hare);
+        hare = f(&next);
+    }
+
+    // The distance between them is now a multiple of the cycle length
+    let mut start = 0;
+    tortoise = x0;
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        hare = f(&hare);
+        start += 1;
+    }
+
+    let mut len = 1;
+    hare = f(&tortoise);
+    while tortoise != hare {
+        hare = f(&hare);
+        len += 1;
+    }
+    Cycle { start, len }
+}
+
+/// Brent's algorithm. Like [`floyd`] it keeps two states at a time, but
+/// needs fewer calls to `f`.
+pub fn brent<T: PartialEq + Clone>(x0: T, mut f: impl FnMut(&T) -> T) -> Cycle {
+    // Find the length by moving the tortoise to the hare at powers of two
+    let (mut power, mut len) = (1, 1);
+    let mut tortoise = x0.clone();
+    let mut hare = f(&x0);
+    while tortoise != hare {
+        if power == len {
+            tortoise = hare.clone();
+            power *= 2;
+            len = 0;
+        }
+        hare = f(&hare);
+        len += 1;
+    }
+
+    // Then walk two states `len` apart until they meet
+    let mut tortoise = x0.clone();
+    let mut hare = x0;
+    for _ in 0..len {
+        hare = f(&hare);
+    }
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        hare = f(&hare);
+        start += 1;
+    }
+    Cycle { start, len }
+}
+
+/// Steps until a state repeats, keeping every state and indexing them by
+/// hash. Calls `f` once per state, which pays off when it is expensive.
+/// Returns the states from `x0` up to the end of the first cycle.
+pub fn find<T: Hash + Eq>(x0: T, mut f: impl FnMut(&T) -> T) -> (Cycle, Vec<T>) {
+    let hasher = RandomState::new();
+    let mut seen: HashMap<u64, Vec<usize>> = HashMap::new();
+    let mut states = vec![];
+    let mut x = x0;
+    loop {
+        let same_hash = seen.entry(hasher.hash_one(&x)).or_default();
+        if let Some(&start) = same_hash.iter().find(|&&i| states[i] == x) {
+            let len = states.len() - start;
+            return (Cycle { start, len }, states);
+        }
+        same_hash.push(states.len());
+        let next = f(&x);
+        states.push(x);
+        x = next;
+    }
+}
+
+/// The state after `n` calls of `f`, without making more calls than it takes
+/// to find the cycle. Never returns if the states don't repeat.
+pub fn nth<T: Hash + Eq>(x0: T, f: impl FnMut(&T) -> T, n: usize) -> T {
+    let (cycle, mut states) = find(x0, f);
+    states.swap_remove(cycle.reduce(n))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{brent, find, floyd, nth, Cycle};
+
+    /// 0, 1, 2, 3, 4, 5, 3, 4, 5, ...
+    fn step(x: &usize) -> usize {
+        if *x == 5 {
+            3
+        } else {
+            x + 1
+        }
+    }
+
+    #[test]
+    fn cycle_detect() {
+        let expected = Cycle { start: 3, len: 3 };
+        assert_eq!(floyd(0, step), expected);
+        assert_eq!(brent(0, step), expected);
+        let (cycle, states) = find(0, step);
+        assert_eq!(cycle, expected);
+        assert_eq!(states, [0, 1, 2, 3, 4, 5]);
+
+        // Starting inside the cycle
+        let expected = Cycle { start: 0, len: 3 };
+        assert_eq!(floyd(4, step), expected);
+        assert_eq!(brent(4, step), expected);
+        assert_eq!(find(4, step).0, expected);
+
+        // A fixed point
+        let expected = Cycle { start: 2, len: 1 };
+        assert_eq!(floyd(0, |x: &u8| (x + 1).min(2)), expected);
+        assert_eq!(brent(0, |x: &u8| (x + 1).min(2)), expected);
+        assert_eq!(find(0, |x: &u8| (x + 1).min(2)).0, expected);
+    }
+
+    #[test]
+    fn cycle_nth() {
+        let cycle = Cycle { start: 3, len: 3 };
+        assert_eq!(cycle.reduce(2), 2);
+        assert_eq!(cycle.reduce(6), 3);
+        assert_eq!(cycle.reduce(1_000_000_000), 4);
+
+        for n in 0..20 {
+            let naive = (0..n).fold(0, |x, _| step(&x));
+            assert_eq!(nth(0, step, n), naive);
+        }
+        assert_eq!(nth(0, step, usize::MAX), 3);
+
+        let mut calls = 0;
+        nth(
+            0,
+            |x| {
+                calls += 1;
+                step(x)
+            },
+            1_000_000_000,
+        );
+        assert_eq!(calls, 6);
+    }
+
+    #[test]
+    fn cycle_hash_collisions() {
+        // States that are equal by hash but not by value must not match
+        #[derive(PartialEq, Eq)]
+        struct Colliding(u32);
+        impl std::hash::Hash for Colliding {
+            fn hash<H: std::hash::Hasher>(&self, _: &mut H) {}
+        }
+        let (cycle, _) = find(Colliding(0), |x| Colliding((x.0 + 1) % 7));
+        assert_eq!(cycle, Cycle { start: 0, len: 7 });
+    }
+}
@@ -0,0 +1,158 @@
+//! Simple polygons with integer corners, for counting the points inside a
+//! loop without visiting them.
+
+use num::Integer;
+
+use super::{Coord, Dir};
+
+/// A closed polygon through `corners`, in order. The last corner connects
+/// back to the first one. Corners may also be plain points along an edge.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Polygon {
+    corners: Vec<Coord>,
+}
+
+impl Polygon {
+    pub fn new(corners: Vec<Coord>) -> Self {
+        Self { corners }
+    }
+
+    /// The polygon traced by walking each `(dir, steps)` in turn from
+    /// `start`. The walk should end where it started.
+    pub fn from_walk(start: Coord, walk: impl IntoIterator<Item = (Dir, isize)>) -> Self {
+        let mut corners = vec![start];
+        let mut cur = start;
+        for (dir, steps) in walk {
+            cur = cur + dir.movement() * steps;
+            corners.push(cur);
+        }
+        if corners.len() > 1 && corners.last() == corners.first() {
+            corners.pop();
+        }
+        Self { corners }
+    }
+
+    fn edges(&self) -> impl Iterator<Item = (Coord, Coord)> + '_ {
+        self.corners
+            .iter()
+            .copied()
+            .zip(self.corners.iter().copied().cycle().skip(1))
+    }
+
+    /// Twice the enclosed area by the shoelace formula, which keeps it an
+    /// integer.
+    pub fn twice_area(&self) -> isize {
+        self.edges()
+            .map(|(a, b)| a.icol() * b.irow() - b.icol() * a.irow())
+            .sum::<isize>()
+            .abs()
+    }
+
+    /// The number of integer points on the edges.
+    pub fn boundary_points(&self) -> isize {
+        self.edges()
+            .map(|(a, b)| (b.irow() - a.irow()).gcd(&(b.icol() - a.icol())))
+            .sum()
+    }
+
+    /// The number of integer points strictly inside, by Pick's theorem:
+    /// A = I + B/2 - 1.
+    pub fn interior_points(&self) -> isize {
+        (self.twice_area() - self.boundary_points() + 2) / 2
+    }
+
+    /// Whether `p` is strictly inside, by its winding number. Points on an
+    /// edge are not.
+    pub fn contains(&self, p: Coord) -> bool {
+        let mut winding = 0;
+        for (a, b) in self.edges() {
+            // Which side of the line a -> b the point is on
+            let side = (b.icol() - a.icol()) * (p.irow() - a.irow())
+                - (p.icol() - a.icol()) * (b.irow() - a.irow());
+            let within = |lo: isize, hi: isize, v: isize| lo.min(hi) <= v && v <= lo.max(hi);
+            if side == 0
+                && within(a.irow(), b.irow(), p.irow())
+                && within(a.icol(), b.icol(), p.icol())
+            {
+                return false;
+            }
+            if a.irow() <= p.irow() {
+                if b.irow() > p.irow() && side > 0 {
+                    winding += 1;
+                }
+            } else if b.irow() <= p.irow() && side < 0 {
+                winding -= 1;
+            }
+        }
+        winding != 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Polygon;
+    use crate::common::{Coord, Dir};
+
+    fn square() -> Polygon {
+        Polygon::from_walk(
+            Coord::new(0, 0),
+            [(Dir::E, 4), (Dir::S, 4), (Dir::W, 4), (Dir::N, 4)],
+        )
+    }
+
+    #[test]
+    fn polygon_counts() {
+        let p = square();
+        assert_eq!(p.twice_area(), 32);
+        assert_eq!(p.boundary_points(), 16);
+        assert_eq!(p.interior_points(), 9);
+
+        // The same square anticlockwise, with an extra point on an edge
+        let p = Polygon::new(
+            [(0, 0), (4, 0), (4, 4), (0, 4), (0, 2)]
+                .map(Coord::from)
+                .to_vec(),
+        );
+        assert_eq!(p.twice_area(), 32);
+        assert_eq!(p.boundary_points(), 16);
+
+        // A triangle with a sloped edge: (0,0), (0,4), (2,0)
+        let p = Polygon::new([(0, 0), (0, 4), (2, 0)].map(Coord::from).to_vec());
+        assert_eq!(p.twice_area(), 8);
+        assert_eq!(p.boundary_points(), 8);
+        assert_eq!(p.interior_points(), 1);
+    }
+
+    #[test]
+    fn polygon_contains() {
+        let p = square();
+        assert!(p.contains(Coord::new(2, 2)));
+        assert!(p.contains(Coord::new(1, 3)));
+        assert!(!p.contains(Coord::new(0, 2)));
+        assert!(!p.contains(Coord::new(4, 4)));
+        assert!(!p.contains(Coord::new(5, 2)));
+        assert!(!p.contains(Coord::new(2, -1)));
+
+        // A U shape, open to the north
+        let u = Polygon::from_walk(
+            Coord::new(0, 0),
+            [
+                (Dir::E, 1),
+                (Dir::S, 2),
+                (Dir::E, 2),
+                (Dir::N, 2),
+                (Dir::E, 1),
+                (Dir::S, 4),
+                (Dir::W, 4),
+                (Dir::N, 4),
+            ],
+        );
+        let inside: Vec<Coord> = (0..=4)
+            .flat_map(|r| (0..=4).map(move |c| Coord::new(r, c)))
+            .filter(|c| u.contains(*c))
+            .collect();
+        assert_eq!(inside.len() as isize, u.interior_points());
+        assert!(!u.contains(Coord::new(1, 2)));
+        assert!(u.contains(Coord::new(3, 2)));
+    }
+}
@@ -0,0 +1,296 @@
+//! Helpers on top of petgraph for puzzles that are graphs in disguise.
+
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::fmt::Display;
+
+use petgraph::graph::{DiGraph, EdgeIndex, Graph, NodeIndex, UnGraph};
+use petgraph::visit::EdgeRef;
+use petgraph::EdgeType;
+
+use super::{Coord, Grid};
+
+/// Builds a graph of named nodes, adding each name only once.
+pub struct GraphBuilder<Ty: EdgeType> {
+    graph: Graph<String, usize, Ty>,
+    index: HashMap<String, NodeIndex>,
+}
+
+impl<Ty: EdgeType> Default for GraphBuilder<Ty> {
+    fn default() -> Self {
+        Self {
+            graph: Graph::default(),
+            index: HashMap::new(),
+        }
+    }
+}
+
+impl<Ty: EdgeType> GraphBuilder<Ty> {
+    /// The node called `name`, added if it's new.
+    pub fn node(&mut self, name: &str) -> NodeIndex {
+        if let Some(n) = self.index.get(name) {
+            return *n;
+        }
+        let n = self.graph.add_node(name.to_string());
+        self.index.insert(name.to_string(), n);
+        n
+    }
+
+    pub fn edge(&mut self, a: &str, b: &str, weight: usize) -> EdgeIndex {
+        let (a, b) = (self.node(a), self.node(b));
+        self.graph.add_edge(a, b, weight)
+    }
+
+    pub fn build(self) -> Graph<String, usize, Ty> {
+        self.graph
+    }
+}
+
+/// Collapses the corridors of a grid maze into weighted edges. `moves` gives
+/// the cells one step can reach from a cell. Cells with more than two moves
+/// become nodes, as do those in `keep`, and each edge is the number of steps
+/// along a corridor from one node to the next. Corridors that end elsewhere
+/// or can't be walked to the end are left out.
+pub fn compress_grid<T>(
+    grid: &Grid<T>,
+    keep: &[Coord],
+    moves: impl Fn(Coord) -> Vec<Coord>,
+) -> DiGraph<Coord, usize> {
+    let mut graph = DiGraph::new();
+    let mut nodes = HashMap::new();
+    for c in grid.coords() {
+        if keep.contains(&c) || moves(c).len() > 2 {
+            nodes.insert(c, graph.add_node(c));
+        }
+    }
+
+    for (&from, &from_idx) in &nodes {
+        'corridor: for first in moves(from) {
+            let (mut prev, mut cur, mut steps) = (from, first, 1);
+            while !nodes.contains_key(&cur) {
+                let mut next = moves(cur).into_iter().filter(|n| *n != prev);
+                match (next.next(), next.next()) {
+                    (Some(n), None) => (prev, cur, steps) = (cur, n, steps + 1),
+                    _ => continue 'corridor,
+                }
+            }
+            graph.add_edge(from_idx, nodes[&cur], steps);
+        }
+    }
+    graph
+}
+
+/// The total weight of the heaviest path from `from` to `to` that visits no
+/// node twice, or `None` if there is no path. Tries every path, so the graph
+/// should be small, e.g. a compressed grid.
+pub fn longest_path<N>(graph: &DiGraph<N, usize>, from: NodeIndex, to: NodeIndex) -> Option<usize> {
+    fn dfs<N>(
+        graph: &DiGraph<N, usize>,
+        cur: NodeIndex,
+        to: NodeIndex,
+        visited: &mut [bool],
+    ) -> Option<usize> {
+        if cur == to {
+            return Some(0);
+        }
+        visited[cur.index()] = true;
+        let mut best = None;
+        for e in graph.edges(cur) {
+            if !visited[e.target().index()] {
+                if let Some(rest) = dfs(graph, e.target(), to, visited) {
+                    best = best.max(Some(e.weight() + rest));
+                }
+            }
+        }
+        visited[cur.index()] = false;
+        best
+    }
+
+    dfs(graph, from, to, &mut vec![false; graph.node_count()])
+}
+
+/// A global minimum cut by Stoer–Wagner: the lightest set of edges whose
+/// removal splits the graph in two. Returns the weight of the cut and the
+/// nodes on one side, or `None` with fewer than two nodes.
+pub fn min_cut<N>(graph: &UnGraph<N, usize>) -> Option<(usize, Vec<NodeIndex>)> {
+    let n = graph.node_count();
+    // Merged nodes keep the members and edges of all the nodes merged in
+    let mut members: Vec<Vec<NodeIndex>> = graph.node_indices().map(|i| vec![i]).collect();
+    let mut adj: Vec<HashMap<usize, usize>> = vec![HashMap::new(); n];
+    for e in graph.edge_references() {
+        let (a, b) = (e.source().index(), e.target().index());
+        if a != b {
+            *adj[a].entry(b).or_default() += e.weight();
+            *adj[b].entry(a).or_default() += e.weight();
+        }
+    }
+
+    let mut active: HashSet<usize> = (0..n).collect();
+    let mut best: Option<(usize, Vec<NodeIndex>)> = None;
+    while active.len() > 1 {
+        // Add the most tightly connected node until all are in, remembering
+        // the last two
+        let first = *active.iter().next()?;
+        let mut added = vec![false; n];
+        let mut links = vec![0; n];
+        let mut heap = BinaryHeap::from([(0, first)]);
+        let (mut s, mut t) = (first, first);
+        while let Some((w, v)) = heap.pop() {
+            if added[v] || w != links[v] {
+                continue;
+            }
+            added[v] = true;
+            (s, t) = (t, v);
+            for (&u, &weight) in &adj[v] {
+                if !added[u] {
+                    links[u] += weight;
+                    heap.push((links[u], u));
+                }
+            }
+        }
+
+        // Disconnected: nothing has to be cut
+        if active.iter().any(|v| !added[*v]) {
+            let side = active
+                .iter()
+                .filter(|v| added[**v])
+                .flat_map(|v| members[*v].iter().copied())
+                .collect();
+            return Some((0, side));
+        }
+
+        // The cut between t and everything else is the lightest between s and t
+        if best.as_ref().is_none_or(|(w, _)| links[t] < *w) {
+            best = Some((links[t], members[t].clone()));
+        }
+
+        // Merge t into s
+        let t_members = std::mem::take(&mut members[t]);
+        members[s].extend(t_members);
+        for (u, weight) in std::mem::take(&mut adj[t]) {
+            adj[u].remove(&t);
+            if u != s {
+                *adj[s].entry(u).or_default() += weight;
+                *adj[u].entry(s).or_default() += weight;
+            }
+        }
+        active.remove(&t);
+    }
+    best
+}
+
+/// The graph in Graphviz DOT format, with the weights as edge labels.
+pub fn to_dot<N: Display, E: Display, Ty: EdgeType>(graph: &Graph<N, E, Ty>) -> String {
+    petgraph::dot::Dot::new(graph).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use petgraph::{Directed, Undirected};
+
+    use super::{compress_grid, longest_path, min_cut, to_dot, GraphBuilder};
+    use crate::common::{Coord, Grid};
+
+    #[test]
+    fn graph_builder() {
+        let mut b = GraphBuilder::<Undirected>::default();
+        let a = b.node("a");
+        b.edge("a", "b", 1);
+        b.edge("b", "c", 2);
+        assert_eq!(b.node("a"), a);
+        let g = b.build();
+        assert_eq!((g.node_count(), g.edge_count()), (3, 2));
+        assert_eq!(g[a], "a");
+
+        let dot = to_dot(&g);
+        assert!(dot.starts_with("graph {"));
+        assert!(dot.contains("label = \"2\""));
+    }
+
+    #[test]
+    fn graph_compress_grid() {
+        let grid: Grid<char> = "#.###\n#...#\n#.#.#\n#...#\n###.#".parse().unwrap();
+        let moves = |c: Coord| -> Vec<Coord> {
+            if grid[c] == '#' {
+                return vec![];
+            }
+            grid.neighbors(c).filter(|n| grid[*n] == '.').collect()
+        };
+        let (start, end) = (Coord::new(0, 1), Coord::new(4, 3));
+        let g = compress_grid(&grid, &[start, end], moves);
+
+        // Two junctions with a corridor each way round the block between them
+        assert_eq!(g.node_count(), 4);
+        let idx = |c: Coord| g.node_indices().find(|i| g[*i] == c).unwrap();
+        let weights = |a, b| -> Vec<usize> {
+            let mut w: Vec<usize> = g
+                .edges_connecting(idx(a), idx(b))
+                .map(|e| *e.weight())
+                .collect();
+            w.sort();
+            w
+        };
+        assert_eq!(weights(start, Coord::new(1, 1)), [1]);
+        assert_eq!(weights(Coord::new(1, 1), Coord::new(3, 3)), [4, 4]);
+        assert_eq!(weights(Coord::new(3, 3), end), [1]);
+        assert_eq!(longest_path(&g, idx(start), idx(end)), Some(6));
+        assert_eq!(longest_path(&g, idx(end), idx(end)), Some(0));
+    }
+
+    #[test]
+    fn graph_longest_path() {
+        let mut b = GraphBuilder::<Directed>::default();
+        b.edge("a", "b", 1);
+        b.edge("b", "c", 1);
+        b.edge("a", "c", 5);
+        b.edge("c", "b", 10);
+        b.edge("b", "a", 100);
+        let (a, c, d) = (b.node("a"), b.node("c"), b.node("d"));
+        let g = b.build();
+        assert_eq!(longest_path(&g, a, c), Some(5));
+        assert_eq!(longest_path(&g, a, d), None);
+    }
+
+    #[test]
+    fn graph_min_cut() {
+        // Two triangles joined by a single edge
+        let mut b = GraphBuilder::<Undirected>::default();
+        for (x, y) in [
+            ("a", "b"),
+            ("b", "c"),
+            ("c", "a"),
+            ("d", "e"),
+            ("e", "f"),
+            ("f", "d"),
+        ] {
+            b.edge(x, y, 1);
+        }
+        b.edge("c", "d", 1);
+        let g = b.build();
+        let (cut, side) = min_cut(&g).unwrap();
+        assert_eq!(cut, 1);
+        let mut names: Vec<&str> = side.iter().map(|i| g[*i].as_str()).collect();
+        names.sort();
+        assert!(names == ["a", "b", "c"] || names == ["d", "e", "f"]);
+
+        // Heavy edges are worth keeping
+        let mut b = GraphBuilder::<Undirected>::default();
+        b.edge("a", "b", 10);
+        b.edge("b", "c", 3);
+        b.edge("c", "a", 2);
+        let g = b.build();
+        let (cut, side) = min_cut(&g).unwrap();
+        assert_eq!(cut, 5);
+        let mut names: Vec<&str> = side.iter().map(|i| g[*i].as_str()).collect();
+        names.sort();
+        assert!(names == ["c"] || names == ["a", "b"]);
+
+        let mut b = GraphBuilder::<Undirected>::default();
+        b.edge("a", "b", 1);
+        b.node("c");
+        assert_eq!(min_cut(&b.build()).unwrap().0, 0);
+
+        let mut b = GraphBuilder::<Undirected>::default();
+        b.node("a");
+        assert_eq!(min_cut(&b.build()), None);
+    }
+}
@@ -0,0 +1,279 @@
+use super::{Bounds, Coord, Neighborhood};
+
+/// A rectangular grid, stored row by row.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    rows: usize,
+    cols: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Builds a grid from its rows, which must all have the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> anyhow::Result<Self> {
+        let cols = rows.first().map_or(0, |row| row.len());
+        let num_rows = rows.len();
+        let mut cells = Vec::with_capacity(num_rows * cols);
+        for (r, row) in rows.into_iter().enumerate() {
+            if row.len() != cols {
+                anyhow::bail!("Row {} has {} cells, expected {cols}", r + 1, row.len());
+            }
+            cells.extend(row);
+        }
+        Ok(Self {
+            rows: num_rows,
+            cols,
+            cells,
+        })
+    }
+
+    /// Parses one cell per character, with a row per line. Errors from `f`
+    /// get the position of the character added.
+    pub fn parse(
+        input: &str,
+        mut f: impl FnMut(char) -> anyhow::Result<T>,
+    ) -> anyhow::Result<Self> {
+        let rows = input
+            .lines()
+            .enumerate()
+            .map(|(r, line)| {
+                line.chars()
+                    .enumerate()
+                    .map(|(c, ch)| {
+                        f(ch).map_err(|e| anyhow::anyhow!("{e} at row {}, col {}", r + 1, c + 1))
+                    })
+                    .collect()
+            })
+            .collect::<anyhow::Result<_>>()?;
+        Self::from_rows(rows)
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    fn offset(&self, c: Coord) -> Option<usize> {
+        let (row, col) = c.index()?;
+        (row < self.rows && col < self.cols).then_some(row * self.cols + col)
+    }
+
+    pub fn get(&self, c: Coord) -> Option<&T> {
+        self.offset(c).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, c: Coord) -> Option<&mut T> {
+        self.offset(c).map(|i| &mut self.cells[i])
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.cols..(row + 1) * self.cols]
+    }
+
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &T> + '_ {
+        self.cells
+            .iter()
+            .skip(col)
+            .step_by(self.cols.max(1))
+            .take(self.rows)
+    }
+
+    pub fn iter_rows(&self) -> impl DoubleEndedIterator<Item = &[T]> + '_ {
+        (0..self.rows).map(|r| self.row(r))
+    }
+
+    pub fn iter_rows_mut(&mut self) -> impl DoubleEndedIterator<Item = &mut [T]> + '_ {
+        self.cells.chunks_mut(self.cols.max(1))
+    }
+
+    pub fn iter_cols(&self) -> impl Iterator<Item = impl Iterator<Item = &T> + '_> + '_ {
+        (0..self.cols).map(|c| self.column(c))
+    }
+
+    pub fn coords(&self) -> impl Iterator<Item = Coord> {
+        let cols = self.cols;
+        (0..self.rows).flat_map(move |r| (0..cols).map(move |c| (r, c).into()))
+    }
+
+    /// Every cell with its coordinate, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Coord, &T)> + '_ {
+        self.coords().zip(self.cells.iter())
+    }
+
+    /// The coordinate of the first cell, row by row, matching `pred`.
+    pub fn position(&self, pred: impl Fn(&T) -> bool) -> Option<Coord> {
+        self.iter().find(|(_, v)| pred(v)).map(|(c, _)| c)
+    }
+
+    /// The coordinates inside the grid, as a rectangle.
+    pub fn bounds(&self) -> Bounds {
+        Bounds::new(
+            Coord::new(0, 0),
+            Coord::new(self.rows as isize - 1, self.cols as isize - 1),
+        )
+    }
+
+    /// The 4-way neighbours of `c` that are inside the grid.
+    pub fn neighbors(&self, c: Coord) -> impl Iterator<Item = Coord> {
+        c.around(Neighborhood::Orthogonal, Some(self.bounds()))
+    }
+
+    /// The 8-way neighbours of `c` that are inside the grid.
+    pub fn neighbors8(&self, c: Coord) -> impl Iterator<Item = Coord> {
+        c.around(Neighborhood::Surrounding, Some(self.bounds()))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            rows: self.rows,
+            cols: self.cols,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl std::str::FromStr for Grid<char> {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s, Ok)
+    }
+}
+
+impl<T> std::ops::Index<Coord> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, c: Coord) -> &Self::Output {
+        let (rows, cols) = (self.rows, self.cols);
+        self.get(c)
+            .unwrap_or_else(|| panic!("{c} is outside the {rows}x{cols} grid"))
+    }
+}
+
+impl<T> std::ops::IndexMut<Coord> for Grid<T> {
+    fn index_mut(&mut self, c: Coord) -> &mut Self::Output {
+        let (rows, cols) = (self.rows, self.cols);
+        self.get_mut(c)
+            .unwrap_or_else(|| panic!("{c} is outside the {rows}x{cols} grid"))
+    }
+}
+
+impl<T: std::fmt::Display> std::fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (r, row) in self.iter_rows().enumerate() {
+            if r > 0 {
+                writeln!(f)?;
+            }
+            for v in row {
+                write!(f, "{v}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Grid;
+    use crate::common::Coord;
+
+    const INPUT: &str = "#..
+.#.
+..#
+##.";
+
+    #[test]
+    fn grid_parse() {
+        let grid: Grid<char> = INPUT.parse().unwrap();
+        assert_eq!((grid.rows(), grid.cols()), (4, 3));
+        assert_eq!(grid[Coord::new(1, 1)], '#');
+        assert_eq!(grid[Coord::new(3, 2)], '.');
+        assert_eq!(grid.to_string(), INPUT);
+
+        let e = "..\n...".parse::<Grid<char>>().unwrap_err().to_string();
+        assert_eq!(e, "Row 2 has 3 cells, expected 2");
+
+        let e = Grid::parse("12\n3x", |c| {
+            c.to_digit(10)
+                .ok_or_else(|| anyhow::anyhow!("Invalid digit '{c}'"))
+        })
+        .unwrap_err()
+        .to_string();
+        assert_eq!(e, "Invalid digit 'x' at row 2, col 2");
+
+        let empty: Grid<char> = "".parse().unwrap();
+        assert_eq!((empty.rows(), empty.cols()), (0, 0));
+        assert_eq!(empty.coords().count(), 0);
+    }
+
+    #[test]
+    fn grid_get() {
+        let mut grid: Grid<char> = INPUT.parse().unwrap();
+        assert_eq!(grid.get(Coord::new(0, 0)), Some(&'#'));
+        assert_eq!(grid.get(Coord::new(-1, 0)), None);
+        assert_eq!(grid.get(Coord::new(0, -1)), None);
+        assert_eq!(grid.get(Coord::new(4, 0)), None);
+        assert_eq!(grid.get(Coord::new(0, 3)), None);
+        // Would be the end of row 0 if the offset wrapped
+        assert_eq!(grid.get(Coord::new(1, -1)), None);
+        assert_eq!(grid.get(Coord::new(isize::MIN, isize::MIN)), None);
+
+        *grid.get_mut(Coord::new(0, 1)).unwrap() = 'x';
+        grid[Coord::new(0, 2)] = 'y';
+        assert_eq!(grid.row(0), &['#', 'x', 'y']);
+        assert!(grid.get_mut(Coord::new(0, -1)).is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "(-1, 0) is outside the 4x3 grid")]
+    fn grid_index_outside() {
+        let grid: Grid<char> = INPUT.parse().unwrap();
+        let _ = grid[Coord::new(-1, 0)];
+    }
+
+    #[test]
+    fn grid_iter() {
+        let grid: Grid<char> = INPUT.parse().unwrap();
+        let rows: Vec<String> = grid.iter_rows().map(|r| r.iter().collect()).collect();
+        assert_eq!(rows, ["#..", ".#.", "..#", "##."]);
+        let cols: Vec<String> = grid.iter_cols().map(|c| c.collect()).collect();
+        assert_eq!(cols, ["#..#", ".#.#", "..#."]);
+
+        let hashes: Vec<Coord> = grid
+            .iter()
+            .filter(|(_, c)| **c == '#')
+            .map(|(c, _)| c)
+            .collect();
+        assert_eq!(hashes.len(), 5);
+        assert_eq!(hashes[3], Coord::new(3, 0));
+        assert_eq!(grid.position(|c| *c == '#'), Some(Coord::new(0, 0)));
+        assert_eq!(grid.position(|c| *c == 'x'), None);
+
+        let mut grid = grid;
+        grid.iter_rows_mut().for_each(|row| row.reverse());
+        assert_eq!(grid.row(3), &['.', '#', '#']);
+        assert_eq!(
+            grid.map(|c| *c == '#').iter().filter(|(_, v)| **v).count(),
+            5
+        );
+    }
+
+    #[test]
+    fn grid_neighbors() {
+        let grid: Grid<char> = "....\n....\n....".parse().unwrap();
+        let corner: Vec<Coord> = grid.neighbors(Coord::new(0, 0)).collect();
+        assert_eq!(corner, [Coord::new(1, 0), Coord::new(0, 1)]);
+        assert_eq!(grid.neighbors(Coord::new(1, 1)).count(), 4);
+        assert_eq!(grid.neighbors(Coord::new(2, 3)).count(), 2);
+        assert_eq!(grid.neighbors(Coord::new(5, 5)).count(), 0);
+
+        assert_eq!(grid.neighbors8(Coord::new(0, 0)).count(), 3);
+        assert_eq!(grid.neighbors8(Coord::new(1, 1)).count(), 8);
+        assert_eq!(grid.neighbors8(Coord::new(2, 2)).count(), 5);
+        assert!(grid.bounds().contains(Coord::new(2, 3)));
+        assert!(!grid.bounds().contains(Coord::new(3, 3)));
+    }
+}
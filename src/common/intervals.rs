@@ -0,0 +1,221 @@
+//! Half-open integer intervals, sets of them and boxes made of them, for
+//! puzzles that are too big to handle one value at a time.
+
+use std::ops::Range;
+
+/// The integers in `start..end`. Empty when `start >= end`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Interval {
+    pub start: i64,
+    pub end: i64,
+}
+
+impl Interval {
+    pub const fn new(start: i64, end: i64) -> Self {
+        Self { start, end }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start >= self.end
+    }
+
+    /// The number of integers in the interval.
+    pub fn len(&self) -> usize {
+        self.end.saturating_sub(self.start).max(0) as usize
+    }
+
+    pub fn intersect(&self, other: &Self) -> Option<Self> {
+        let i = Self::new(self.start.max(other.start), self.end.min(other.end));
+        (!i.is_empty()).then_some(i)
+    }
+
+    /// The parts below `at` and from `at` on, if not empty.
+    pub fn split_at(&self, at: i64) -> (Option<Self>, Option<Self>) {
+        let at = at.clamp(self.start, self.end.max(self.start));
+        let below = Self::new(self.start, at);
+        let above = Self::new(at, self.end);
+        (
+            (!below.is_empty()).then_some(below),
+            (!above.is_empty()).then_some(above),
+        )
+    }
+
+    pub fn offset(&self, by: i64) -> Self {
+        Self::new(self.start + by, self.end + by)
+    }
+}
+
+impl From<Range<i64>> for Interval {
+    fn from(r: Range<i64>) -> Self {
+        Self::new(r.start, r.end)
+    }
+}
+
+/// A set of integers, kept as sorted, disjoint and non-adjacent intervals.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct IntervalSet {
+    intervals: Vec<Interval>,
+}
+
+impl IntervalSet {
+    pub fn iter(&self) -> impl Iterator<Item = &Interval> {
+        self.intervals.iter()
+    }
+
+    pub fn min(&self) -> Option<i64> {
+        self.intervals.first().map(|i| i.start)
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        self.iter().chain(other.iter()).copied().collect()
+    }
+
+    /// The set without the integers in `other`.
+    pub fn subtract(&self, other: &Interval) -> Self {
+        self.iter()
+            .flat_map(|i| {
+                let (below, _) = i.split_at(other.start);
+                let (_, above) = i.split_at(other.end);
+                [below, above]
+            })
+            .flatten()
+            .collect()
+    }
+}
+
+impl FromIterator<Interval> for IntervalSet {
+    fn from_iter<I: IntoIterator<Item = Interval>>(iter: I) -> Self {
+        let mut sorted: Vec<Interval> = iter.into_iter().filter(|i| !i.is_empty()).collect();
+        sorted.sort_by_key(|i| i.start);
+
+        let mut intervals: Vec<Interval> = Vec::with_capacity(sorted.len());
+        for i in sorted {
+            match intervals.last_mut() {
+                Some(last) if i.start <= last.end => last.end = last.end.max(i.end),
+                _ => intervals.push(i),
+            }
+        }
+        Self { intervals }
+    }
+}
+
+/// Moves values in some source intervals by an offset each, leaving all other
+/// values as they are. Where sources overlap, the first one wins.
+#[derive(Clone, Debug, Default)]
+pub struct OffsetTable {
+    entries: Vec<(Interval, i64)>,
+}
+
+impl OffsetTable {
+    pub fn new(entries: impl IntoIterator<Item = (Interval, i64)>) -> Self {
+        Self {
+            entries: entries.into_iter().collect(),
+        }
+    }
+
+    pub fn map(&self, set: &IntervalSet) -> IntervalSet {
+        let mut moved = vec![];
+        let mut rest = set.clone();
+        for (src, offset) in &self.entries {
+            moved.extend(
+                rest.iter()
+                    .filter_map(|i| i.intersect(src))
+                    .map(|i| i.offset(*offset)),
+            );
+            rest = rest.subtract(src);
+        }
+        rest.union(&moved.into_iter().collect())
+    }
+}
+
+/// An `N` dimensional box, the product of an interval per axis.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Cuboid<const N: usize> {
+    pub axes: [Interval; N],
+}
+
+impl<const N: usize> Cuboid<N> {
+    pub const fn new(axes: [Interval; N]) -> Self {
+        Self { axes }
+    }
+
+    /// The number of integer points inside.
+    pub fn volume(&self) -> usize {
+        self.axes.iter().map(Interval::len).product()
+    }
+
+    /// The parts below `at` and from `at` on along `axis`, if not empty.
+    pub fn split(&self, axis: usize, at: i64) -> (Option<Self>, Option<Self>) {
+        let (below, above) = self.axes[axis].split_at(at);
+        let with = |i: Interval| {
+            let mut axes = self.axes;
+            axes[axis] = i;
+            Self::new(axes)
+        };
+        (below.map(with), above.map(with))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Cuboid, Interval, IntervalSet, OffsetTable};
+
+    fn set(ranges: &[(i64, i64)]) -> IntervalSet {
+        ranges.iter().map(|&(s, e)| Interval::new(s, e)).collect()
+    }
+
+    #[test]
+    fn interval_ops() {
+        let i = Interval::new(2, 8);
+        assert_eq!(i.len(), 6);
+        assert_eq!(Interval::new(5, 2).len(), 0);
+        assert!(Interval::new(3, 3).is_empty());
+        assert_eq!(i.intersect(&(5..20).into()), Some((5..8).into()));
+        assert_eq!(i.intersect(&(8..20).into()), None);
+        assert_eq!(i.split_at(4), (Some((2..4).into()), Some((4..8).into())));
+        assert_eq!(i.split_at(2), (None, Some(i)));
+        assert_eq!(i.split_at(100), (Some(i), None));
+        assert_eq!(i.offset(-2), (0..6).into());
+    }
+
+    #[test]
+    fn interval_set() {
+        let s = set(&[(5, 7), (0, 2), (1, 3), (7, 9), (20, 20)]);
+        let expected: Vec<Interval> = vec![(0..3).into(), (5..9).into()];
+        assert_eq!(s.iter().copied().collect::<Vec<_>>(), expected);
+        assert_eq!(s.min(), Some(0));
+        assert_eq!(IntervalSet::default().min(), None);
+
+        assert_eq!(s.union(&set(&[(3, 5), (10, 11)])), set(&[(0, 9), (10, 11)]));
+        assert_eq!(s.subtract(&(1..6).into()), set(&[(0, 1), (6, 9)]));
+        assert_eq!(s.subtract(&(-5..50).into()), IntervalSet::default());
+    }
+
+    #[test]
+    fn interval_offset_table() {
+        // day5's seed-to-soil map
+        let table = OffsetTable::new([((98..100).into(), -48), ((50..98).into(), 2)]);
+        assert_eq!(table.map(&set(&[(79, 93)])), set(&[(81, 95)]));
+        assert_eq!(
+            table.map(&set(&[(0, 10), (97, 102)])),
+            set(&[(0, 10), (50, 52), (99, 100), (100, 102)])
+        );
+
+        // The first entry wins where they overlap
+        let table = OffsetTable::new([((0..10).into(), 100), ((5..15).into(), 1000)]);
+        assert_eq!(
+            table.map(&set(&[(0, 15)])),
+            set(&[(100, 110), (1010, 1015)])
+        );
+    }
+
+    #[test]
+    fn interval_cuboid() {
+        let c = Cuboid::new([(0..10).into(), (0..5).into(), (1..3).into()]);
+        assert_eq!(c.volume(), 100);
+        let (below, above) = c.split(1, 2);
+        assert_eq!(below.unwrap().volume() + above.unwrap().volume(), 100);
+        assert_eq!(below.unwrap().axes[1], (0..2).into());
+        assert_eq!(c.split(2, 0), (None, Some(c)));
+    }
+}
@@ -0,0 +1,192 @@
+//! Number theory and polynomial helpers. Everything is exact and reports
+//! overflow as `None` rather than wrapping.
+
+use std::ops::RangeInclusive;
+
+use num::integer::{Integer, Roots};
+
+/// The greatest common divisor of all of `nums`, 0 if there are none.
+pub fn gcd(nums: &[usize]) -> usize {
+    nums.iter().fold(0, |acc, n| acc.gcd(n))
+}
+
+/// The least common multiple of all of `nums`, 1 if there are none, or
+/// `None` if it doesn't fit in a `usize`.
+pub fn lcm(nums: &[usize]) -> Option<usize> {
+    nums.iter().try_fold(1usize, |acc, &n| {
+        if n == 0 {
+            return Some(0);
+        }
+        (acc / gcd(&[acc, n])).checked_mul(n)
+    })
+}
+
+/// Solves `x ≡ residue (mod modulus)` for all `(residue, modulus)` pairs.
+/// The moduli don't have to be coprime. Returns the smallest solution and
+/// the lcm of the moduli, which is the period of all solutions, or `None` if
+/// the congruences contradict each other, a modulus is 0 or the lcm
+/// overflows.
+pub fn crt(congruences: &[(usize, usize)]) -> Option<(usize, usize)> {
+    congruences
+        .iter()
+        .try_fold((0, 1), |(x, m): (usize, usize), &(r, n)| {
+            if n == 0 {
+                return None;
+            }
+            // x + m * k ≡ r (mod n), so m * k ≡ r - x (mod n)
+            let g = gcd(&[m, n]);
+            let diff = r as i128 - x as i128;
+            if diff % g as i128 != 0 {
+                return None;
+            }
+            let n_g = (n / g) as i128;
+            let k = (diff / g as i128 * inverse((m / g) as i128, n_g)?).mod_floor(&n_g);
+            let period = lcm(&[m, n])?;
+            let x = (x as i128 + m as i128 * k).mod_floor(&(period as i128));
+            Some((x.try_into().ok()?, period))
+        })
+}
+
+/// The inverse of `a` modulo `n`, if they are coprime.
+fn inverse(a: i128, n: i128) -> Option<i128> {
+    let e = a.extended_gcd(&n);
+    (e.gcd == 1).then(|| e.x.mod_floor(&n))
+}
+
+/// The integers strictly between the two real roots of `a x² + b x + c`,
+/// i.e. where it has the opposite sign of `a`. `None` if there are no such
+/// integers, `a` is 0 or the discriminant doesn't fit in an `i128`.
+pub fn between_roots(a: i64, b: i64, c: i64) -> Option<RangeInclusive<i64>> {
+    let (a, b, c) = match a.signum() {
+        0 => return None,
+        1 => (a as i128, b as i128, c as i128),
+        _ => (-(a as i128), -(b as i128), -(c as i128)),
+    };
+    let disc = b
+        .checked_mul(b)?
+        .checked_sub(a.checked_mul(c)?.checked_mul(4)?)?;
+    if disc <= 0 {
+        return None;
+    }
+
+    // x is between the roots iff |2ax + b| < √disc
+    let s = disc.sqrt();
+    let max = if s * s == disc { s - 1 } else { s };
+    let lo = -Integer::div_floor(&(b + max), &(2 * a));
+    let hi = Integer::div_floor(&(max - b), &(2 * a));
+    (lo <= hi).then_some(lo.try_into().ok()?..=hi.try_into().ok()?)
+}
+
+/// The value at `x` of the lowest degree polynomial through
+/// `(0, values[0]), (1, values[1]), ...`, the same one Lagrange
+/// interpolation gives. Uses Newton's forward differences, which stay
+/// integers. `None` on overflow or without values.
+pub fn extrapolate(values: &[i64], x: i64) -> Option<i64> {
+    if values.is_empty() {
+        return None;
+    }
+
+    // The leading value of every row of differences
+    let mut diffs = Vec::with_capacity(values.len());
+    let mut row = values.to_vec();
+    while !row.is_empty() && !row.iter().all(|v| *v == 0) {
+        diffs.push(row[0]);
+        row = row
+            .windows(2)
+            .map(|w| w[1].checked_sub(w[0]))
+            .collect::<Option<_>>()?;
+    }
+
+    // P(x) = Σ Δᵏ·C(x, k), with C(x, k) = C(x, k - 1)·(x - k + 1)/k
+    let (x, mut binom, mut sum) = (x as i128, 1i128, 0i128);
+    for (k, d) in diffs.into_iter().enumerate() {
+        if k > 0 {
+            binom = binom.checked_mul(x - k as i128 + 1)? / k as i128;
+        }
+        sum = sum.checked_add(binom.checked_mul(d as i128)?)?;
+    }
+    sum.try_into().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{between_roots, crt, extrapolate, gcd, lcm};
+    use proptest::prelude::*;
+
+    #[test]
+    fn math_examples() {
+        assert_eq!(gcd(&[12, 18, 27]), 3);
+        assert_eq!(gcd(&[]), 0);
+        assert_eq!(lcm(&[4, 6, 10]), Some(60));
+        assert_eq!(lcm(&[]), Some(1));
+        assert_eq!(lcm(&[5, 0]), Some(0));
+        assert_eq!(lcm(&[usize::MAX, usize::MAX - 1]), None);
+
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt(&[(1, 4), (3, 6)]), Some((9, 12)));
+        assert_eq!(crt(&[(1, 4), (2, 6)]), None);
+        assert_eq!(crt(&[(1, 0)]), None);
+        assert_eq!(crt(&[]), Some((0, 1)));
+
+        assert_eq!(between_roots(1, -7, 9), Some(2..=5));
+        assert_eq!(between_roots(-1, 30, -200), Some(11..=19));
+        assert_eq!(between_roots(1, 0, -4), Some(-1..=1));
+        assert_eq!(between_roots(1, 0, 4), None);
+        assert_eq!(between_roots(1, -2, 1), None);
+        assert_eq!(between_roots(0, 1, 1), None);
+
+        assert_eq!(extrapolate(&[0, 3, 6, 9, 12, 15], 6), Some(18));
+        assert_eq!(extrapolate(&[10, 13, 16, 21, 30, 45], -1), Some(5));
+        assert_eq!(extrapolate(&[7], 100), Some(7));
+        assert_eq!(extrapolate(&[], 0), None);
+        assert_eq!(extrapolate(&[0, i64::MAX], 2), None);
+    }
+
+    proptest! {
+        #[test]
+        fn math_gcd_lcm(nums in prop::collection::vec(1..40usize, 1..5)) {
+            let g = gcd(&nums);
+            let l = lcm(&nums).unwrap();
+            for n in &nums {
+                prop_assert_eq!(n % g, 0);
+                prop_assert_eq!(l % n, 0);
+            }
+            prop_assert!((1..l).all(|m| nums.iter().any(|n| m % n != 0)));
+        }
+
+        #[test]
+        fn math_crt(congruences in prop::collection::vec((0..30usize, 1..30usize), 1..4)) {
+            let moduli: Vec<usize> = congruences.iter().map(|c| c.1).collect();
+            let solutions: Vec<usize> = (0..lcm(&moduli).unwrap())
+                .filter(|x| congruences.iter().all(|(r, n)| x % n == r % n))
+                .collect();
+            match crt(&congruences) {
+                Some((x, m)) => {
+                    prop_assert_eq!(solutions.first(), Some(&x));
+                    prop_assert_eq!(solutions.len(), 1);
+                    prop_assert!(congruences.iter().all(|(_, n)| m % n == 0));
+                }
+                None => prop_assert!(solutions.is_empty()),
+            }
+        }
+
+        #[test]
+        fn math_between_roots(a in -20..20i64, b in -200..200i64, c in -200..200i64) {
+            let f = |x: i64| a * x * x + b * x + c;
+            let brute: Vec<i64> = (-300..=300)
+                .filter(|x| f(*x).signum() * a.signum() < 0)
+                .collect();
+            match between_roots(a, b, c) {
+                Some(range) => prop_assert_eq!(range.collect::<Vec<_>>(), brute),
+                None => prop_assert!(a == 0 || brute.is_empty()),
+            }
+        }
+
+        #[test]
+        fn math_extrapolate(coeffs in prop::collection::vec(-100..100i64, 1..6), x in -20..20i64) {
+            let poly = |x: i64| coeffs.iter().rev().fold(0, |acc, c| acc * x + c);
+            let values: Vec<i64> = (0..coeffs.len() as i64 + 2).map(poly).collect();
+            prop_assert_eq!(extrapolate(&values, x), Some(poly(x)));
+        }
+    }
+}
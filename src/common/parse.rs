@@ -0,0 +1,342 @@
+//! nom parsers for the shapes puzzle inputs keep coming in, and turning
+//! nom's errors into something a person can read.
+
+use std::fmt;
+
+use nom::{
+    branch::alt,
+    bytes::complete::{tag, take_till1},
+    character::complete::{alphanumeric1, char, newline, none_of, space0, space1},
+    combinator::{map_opt, map_res, recognize},
+    error::{VerboseError, VerboseErrorKind},
+    multi::{many1, many1_count, separated_list1},
+    sequence::{pair, separated_pair, tuple},
+};
+
+use super::Grid;
+use crate::{Input, PResult};
+
+/// Runs `parser` over all of `input`, allowing only whitespace after what it
+/// parses. Byte order marks and CRLF line endings are cleaned up when the
+/// input is loaded, see [`crate::input::normalize`].
+pub fn parse_complete<O>(
+    input: &str,
+    mut parser: impl for<'a> FnMut(Input<'a>) -> PResult<'a, O>,
+) -> Result<O, ParseError> {
+    let (rest, value) = parser(input).map_err(|e| error(input, e))?;
+    let rest = rest.trim_start();
+    if !rest.is_empty() {
+        let e = VerboseError {
+            errors: vec![(rest, VerboseErrorKind::Nom(nom::error::ErrorKind::Eof))],
+        };
+        return Err(error(input, nom::Err::Error(e)));
+    }
+    Ok(value)
+}
+
+/// Integers separated by spaces, commas or both, like `7  15 30`, `1,1,3`
+/// or `19, 13, -2`. Fails on values that don't fit in a `T`.
+pub fn ints<T: TryFrom<i64>>(i: Input) -> PResult<Vec<T>> {
+    let sep = alt((recognize(tuple((space0, char(','), space0))), space1));
+    separated_list1(sep, map_res(nom::character::complete::i64, T::try_from))(i)
+}
+
+/// A `key: values` line, such as `jqt: rhn xhk nvd`. The key is everything
+/// up to the colon.
+pub fn key_values<'a, O>(
+    values: impl FnMut(Input<'a>) -> PResult<'a, O>,
+) -> impl FnMut(Input<'a>) -> PResult<'a, (&'a str, O)> {
+    separated_pair(
+        take_till1(|c| c == ':' || c == '\n'),
+        pair(char(':'), space0),
+        values,
+    )
+}
+
+/// The end of a line followed by one or more empty lines.
+pub fn blank_line(i: Input) -> PResult<()> {
+    let (i, _) = pair(newline, many1_count(newline))(i)?;
+    Ok((i, ()))
+}
+
+/// Blocks of lines separated by blank lines, each parsed by `block`.
+pub fn blocks<'a, O>(
+    block: impl FnMut(Input<'a>) -> PResult<'a, O>,
+) -> impl FnMut(Input<'a>) -> PResult<'a, Vec<O>> {
+    separated_list1(blank_line, block)
+}
+
+/// A grid with a row per line and a cell per character, as `cell` turns
+/// it into one. Stops at an empty line and fails on characters `cell`
+/// rejects or rows of different lengths.
+pub fn grid<'a, T>(
+    cell: impl Fn(char) -> Option<T>,
+) -> impl FnMut(Input<'a>) -> PResult<'a, Grid<T>> {
+    move |i| {
+        let row = many1(map_opt(none_of("\n"), &cell));
+        map_res(separated_list1(newline, row), Grid::from_rows)(i)
+    }
+}
+
+/// A `name -> a, b, c` line. The name is everything up to the arrow, any
+/// prefix included, so callers with prefixes like day20's `%` and `&` parse
+/// those first.
+pub fn adjacency<'a>(i: Input<'a>) -> PResult<'a, (&'a str, Vec<&'a str>)> {
+    let arrow = tuple((space0, tag("->"), space0));
+    let sep = tuple((space0, char(','), space0));
+    separated_pair(
+        take_till1(|c: char| c.is_whitespace() || c == '-'),
+        arrow,
+        separated_list1(sep, alphanumeric1),
+    )(i)
+}
+
+/// Where and why parsing failed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    /// 1-based, like an editor.
+    pub line: usize,
+    /// 1-based, in characters.
+    pub column: usize,
+    pub message: String,
+    /// The whole line the error is on.
+    pub source_line: String,
+    /// The `context`s the error happened in, innermost first, with their
+    /// line and column.
+    pub contexts: Vec<(&'static str, usize, usize)>,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )?;
+        let gutter = " ".repeat(self.line.to_string().len());
+        let indent: String = self
+            .source_line
+            .chars()
+            .take(self.column - 1)
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect();
+        writeln!(f, "{gutter} |")?;
+        writeln!(f, "{} | {}", self.line, self.source_line)?;
+        write!(f, "{gutter} | {indent}^")?;
+        for (context, line, column) in &self.contexts {
+            write!(f, "\nin {context} at line {line}, column {column}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Locates the error `e` from parsing `input`. Everything a parser returns
+/// has to be a slice of the end of `input` for this to work, which holds
+/// for all parsers on `crate::Input`.
+pub fn error(input: &str, e: nom::Err<VerboseError<&str>>) -> ParseError {
+    let errors = match e {
+        nom::Err::Error(e) | nom::Err::Failure(e) => e.errors,
+        nom::Err::Incomplete(_) => vec![],
+    };
+
+    let message = errors
+        .iter()
+        .find_map(|(rest, kind)| match kind {
+            VerboseErrorKind::Context(_) => None,
+            VerboseErrorKind::Char(c) => Some(expected(&format!("'{c}'"), rest)),
+            VerboseErrorKind::Nom(kind) => Some(expected(kind.description(), rest)),
+        })
+        .unwrap_or_else(|| "unexpected end of input".to_string());
+    let rest = errors.first().map_or("", |(rest, _)| rest);
+    let (line, column, source_line) = locate(input, rest);
+
+    let contexts = errors
+        .iter()
+        .filter_map(|(rest, kind)| match kind {
+            VerboseErrorKind::Context(context) => {
+                let (line, column, _) = locate(input, rest);
+                Some((*context, line, column))
+            }
+            _ => None,
+        })
+        .collect();
+
+    ParseError {
+        line,
+        column,
+        message,
+        source_line: source_line.to_string(),
+        contexts,
+    }
+}
+
+fn expected(what: &str, rest: &str) -> String {
+    match rest.chars().next() {
+        Some(c) => format!("expected {what}, found {c:?}"),
+        None => format!("expected {what}, found the end of input"),
+    }
+}
+
+/// The line, column and text of the line where `rest` starts.
+fn locate<'a>(input: &'a str, rest: &str) -> (usize, usize, &'a str) {
+    let offset = input.len().saturating_sub(rest.len());
+    let before = &input[..offset];
+    let start = before.rfind('\n').map_or(0, |p| p + 1);
+    let end = input[offset..]
+        .find('\n')
+        .map_or(input.len(), |p| offset + p);
+    let line = before.matches('\n').count() + 1;
+    let column = before[start..].chars().count() + 1;
+    (line, column, input[start..end].trim_end_matches('\r'))
+}
+
+#[cfg(test)]
+mod tests {
+    use nom::{
+        bytes::complete::tag, character::complete::newline, error::context, multi::separated_list1,
+        sequence::preceded,
+    };
+
+    use super::{adjacency, blank_line, blocks, error, grid, ints, key_values, parse_complete};
+    use crate::common::Coord;
+
+    #[test]
+    fn parse_ints() {
+        assert_eq!(ints::<i64>("7  15   30\n1"), Ok(("\n1", vec![7, 15, 30])));
+        assert_eq!(
+            ints::<i64>("19, 13, -2 @ 1"),
+            Ok((" @ 1", vec![19, 13, -2]))
+        );
+        assert_eq!(ints::<usize>("1,1,3 |"), Ok((" |", vec![1, 1, 3])));
+        assert_eq!(ints::<u8>("1 2 300"), Ok((" 300", vec![1, 2])));
+        assert!(ints::<usize>("-1 2").is_err());
+        assert!(ints::<i64>(" 1").is_err());
+    }
+
+    #[test]
+    fn parse_key_values() {
+        let words = separated_list1(tag(" "), nom::character::complete::alpha1);
+        assert_eq!(
+            key_values(words)("jqt: rhn xhk\nrsh"),
+            Ok(("\nrsh", ("jqt", vec!["rhn", "xhk"])))
+        );
+        assert_eq!(
+            key_values(ints::<u32>)("Time:      7  15"),
+            Ok(("", ("Time", vec![7, 15])))
+        );
+        assert!(key_values(ints::<u32>)(": 1").is_err());
+        assert!(key_values(ints::<u32>)("a\nb: 1").is_err());
+    }
+
+    #[test]
+    fn parse_blocks() {
+        assert_eq!(blank_line("\n\n\nx"), Ok(("x", ())));
+        assert!(blank_line("\nx").is_err());
+
+        let mut p = blocks(separated_list1(newline, ints::<u32>));
+        assert_eq!(
+            p("1 2\n3\n\n4\n"),
+            Ok(("\n", vec![vec![vec![1, 2], vec![3]], vec![vec![4]]]))
+        );
+        assert_eq!(p("1\n\n\n2"), Ok(("", vec![vec![vec![1]], vec![vec![2]]])));
+        assert_eq!(p("1\n\nx"), Ok(("\n\nx", vec![vec![vec![1]]])));
+    }
+
+    #[test]
+    fn parse_grid() {
+        let cell = |c: char| c.to_digit(10);
+        let (rest, g) = grid(cell)("12\n34\n\n5").unwrap();
+        assert_eq!(rest, "\n\n5");
+        assert_eq!((g.rows(), g.cols()), (2, 2));
+        assert_eq!(g[Coord::new(1, 0)], 3);
+        assert!(grid(cell)("12\n345").is_err());
+        assert!(grid(cell)("12\n3x").is_err());
+        assert_eq!(grid(cell)("12\nx").unwrap().0, "\nx");
+
+        let (_, maps) =
+            blocks(grid(|c| matches!(c, '.' | '#').then_some(c)))("#.\n.#\n\n##").unwrap();
+        assert_eq!(maps.len(), 2);
+        assert_eq!(maps[1].to_string(), "##");
+    }
+
+    #[test]
+    fn parse_adjacency() {
+        assert_eq!(
+            adjacency("%a -> inv, con\n"),
+            Ok(("\n", ("%a", vec!["inv", "con"])))
+        );
+        assert_eq!(
+            adjacency("broadcaster->a,b"),
+            Ok(("", ("broadcaster", vec!["a", "b"])))
+        );
+        assert!(adjacency("a -> ").is_err());
+        assert!(adjacency("-> a").is_err());
+    }
+
+    fn parse(i: crate::Input) -> crate::PResult<Vec<u32>> {
+        let item = preceded(tag("n="), nom::character::complete::u32);
+        context("list", separated_list1(newline, context("item", item)))(i)
+    }
+
+    #[test]
+    fn parse_error_location() {
+        let input = "n=1\nn=x";
+        let (rest, _) = parse(input).unwrap();
+        assert_eq!(rest, "\nn=x");
+
+        let input = "m=1\nn=2";
+        let e = error(input, parse(input).unwrap_err());
+        assert_eq!((e.line, e.column), (1, 1));
+        assert_eq!(e.message, "expected Tag, found 'm'");
+        assert_eq!(e.source_line, "m=1");
+        assert_eq!(e.contexts, [("item", 1, 1), ("list", 1, 1)]);
+        assert_eq!(
+            e.to_string(),
+            "line 1, column 1: expected Tag, found 'm'
+  |
+1 | m=1
+  | ^
+in item at line 1, column 1
+in list at line 1, column 1"
+        );
+
+        let input = "n=\r\n";
+        let e = error(input, parse(input).unwrap_err());
+        assert_eq!((e.line, e.column), (1, 3));
+        assert_eq!(e.message, "expected Digit, found '\\r'");
+        assert_eq!(e.source_line, "n=");
+
+        let input = "n=";
+        let e = error(input, parse(input).unwrap_err());
+        assert_eq!(e.message, "expected Digit, found the end of input");
+        assert!(e.to_string().contains("\n  |   ^\n"));
+    }
+
+    #[test]
+    fn parse_complete_input() {
+        assert_eq!(parse_complete("n=1\nn=2\n\n", parse), Ok(vec![1, 2]));
+
+        // A line that doesn't parse ends the list, and is left over
+        let e = parse_complete("n=1\r\nn=x\r\n", parse).unwrap_err();
+        assert_eq!((e.line, e.column), (2, 1));
+        assert_eq!(e.message, "expected End of file, found 'n'");
+        assert_eq!(e.source_line, "n=x");
+
+        let e = parse_complete("n=1 \n  junk", parse).unwrap_err();
+        assert_eq!((e.line, e.column), (2, 3));
+        assert!(e.contexts.is_empty());
+
+        let e = parse_complete("\u{feff}m=1", parse).unwrap_err();
+        assert_eq!((e.line, e.column, e.contexts.len()), (1, 1, 2));
+    }
+
+    #[test]
+    fn parse_error_incomplete() {
+        let e = error("ab\ncd", nom::Err::Incomplete(nom::Needed::Unknown));
+        assert_eq!((e.line, e.column), (2, 3));
+        assert_eq!(e.message, "unexpected end of input");
+        assert!(e.contexts.is_empty());
+    }
+}
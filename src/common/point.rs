@@ -0,0 +1,178 @@
+use num_traits::{NumCast, Signed, ToPrimitive};
+
+/// A point or offset in 2D. On grids the fields are row and column.
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq)]
+pub struct Coord2<T>(pub T, pub T);
+
+/// A point or offset in 3D.
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq)]
+pub struct Coord3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T> Coord2<T> {
+    pub const fn new(a: T, b: T) -> Self {
+        Self(a, b)
+    }
+}
+
+impl<T> Coord3<T> {
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Self { x, y, z }
+    }
+}
+
+/// Operators, norms and conversions shared by the point types, given the
+/// type and its fields.
+macro_rules! point {
+    ($name:ident, $vector:ident, $($f:tt),+) => {
+        impl<T: std::ops::Add<Output = T>> std::ops::Add for $name<T> {
+            type Output = Self;
+
+            fn add(self, rhs: Self) -> Self::Output {
+                $name { $($f: self.$f + rhs.$f),+ }
+            }
+        }
+
+        impl<T: std::ops::Sub<Output = T>> std::ops::Sub for $name<T> {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self::Output {
+                $name { $($f: self.$f - rhs.$f),+ }
+            }
+        }
+
+        /// Scaling by a scalar
+        impl<T: std::ops::Mul<Output = T> + Copy> std::ops::Mul<T> for $name<T> {
+            type Output = Self;
+
+            fn mul(self, rhs: T) -> Self::Output {
+                $name { $($f: self.$f * rhs),+ }
+            }
+        }
+
+        impl<T: std::ops::Neg<Output = T>> std::ops::Neg for $name<T> {
+            type Output = Self;
+
+            fn neg(self) -> Self::Output {
+                $name { $($f: -self.$f),+ }
+            }
+        }
+
+        impl<T: ToPrimitive + Copy> $name<T> {
+            /// Converts every field, or `None` if one doesn't fit in `U`.
+            pub fn cast<U: NumCast>(self) -> Option<$name<U>> {
+                Some($name { $($f: U::from(self.$f)?),+ })
+            }
+
+            pub fn euclidean_norm(&self) -> f64 {
+                (0.0 $(+ self.$f.to_f64().unwrap_or(f64::NAN).powi(2))+).sqrt()
+            }
+        }
+
+        impl<T: Signed + PartialOrd + Copy> $name<T> {
+            pub fn manhattan_norm(&self) -> T {
+                T::zero() $(+ self.$f.abs())+
+            }
+
+            pub fn chebyshev_norm(&self) -> T {
+                let mut max = T::zero();
+                $(
+                    if self.$f.abs() > max {
+                        max = self.$f.abs();
+                    }
+                )+
+                max
+            }
+        }
+
+        impl<T: nalgebra::Scalar> From<$name<T>> for nalgebra::$vector<T> {
+            fn from(c: $name<T>) -> Self {
+                nalgebra::$vector::new($(c.$f),+)
+            }
+        }
+
+        impl<T: nalgebra::Scalar + Copy> From<nalgebra::$vector<T>> for $name<T> {
+            fn from(v: nalgebra::$vector<T>) -> Self {
+                let mut fields = v.iter().copied();
+                $name { $($f: fields.next().expect("vector has a value per field")),+ }
+            }
+        }
+    };
+}
+
+point!(Coord2, Vector2, 0, 1);
+point!(Coord3, Vector3, x, y, z);
+
+impl<T: std::fmt::Display> std::fmt::Display for Coord2<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.0, self.1)
+    }
+}
+
+impl<T: std::fmt::Display> std::fmt::Display for Coord3<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {}, {})", self.x, self.y, self.z)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Coord2, Coord3};
+
+    #[test]
+    fn point_ops() {
+        let a = Coord2::new(1, -2);
+        let b = Coord2::new(3, 4);
+        assert_eq!(a + b, Coord2::new(4, 2));
+        assert_eq!(a - b, Coord2::new(-2, -6));
+        assert_eq!(b * 3, Coord2::new(9, 12));
+        assert_eq!(-a, Coord2::new(-1, 2));
+
+        let p = Coord3::new(1.0, 2.0, 3.0);
+        let v = Coord3::new(0.5, -1.0, 0.0);
+        assert_eq!(p + v * 2.0, Coord3::new(2.0, 0.0, 3.0));
+        assert_eq!(p.to_string(), "(1, 2, 3)");
+    }
+
+    #[test]
+    fn point_norms() {
+        let a = Coord2::new(-3i64, 4);
+        assert_eq!(a.manhattan_norm(), 7);
+        assert_eq!(a.chebyshev_norm(), 4);
+        assert_eq!(a.euclidean_norm(), 5.0);
+
+        let b = Coord3::new(2, -3, 6);
+        assert_eq!(b.manhattan_norm(), 11);
+        assert_eq!(b.chebyshev_norm(), 6);
+        assert_eq!(b.euclidean_norm(), 7.0);
+        assert_eq!(Coord3::<i32>::default().chebyshev_norm(), 0);
+    }
+
+    #[test]
+    fn point_cast() {
+        assert_eq!(
+            Coord2::new(-1isize, 5).cast::<i32>(),
+            Some(Coord2::new(-1, 5))
+        );
+        assert_eq!(Coord2::new(-1isize, 5).cast::<usize>(), None);
+        assert_eq!(Coord2::new(1u64 << 40, 0).cast::<u32>(), None);
+        assert_eq!(
+            Coord3::new(1i64, 2, 3).cast::<f64>(),
+            Some(Coord3::new(1.0, 2.0, 3.0))
+        );
+        assert_eq!(Coord3::new(1.5, f64::NAN, 0.0).cast::<i64>(), None);
+    }
+
+    #[test]
+    fn point_nalgebra() {
+        let v: nalgebra::Vector3<f64> = Coord3::new(1.0, 2.0, 3.0).into();
+        assert_eq!(v, nalgebra::Vector3::new(1.0, 2.0, 3.0));
+        assert_eq!(Coord3::from(v * 2.0), Coord3::new(2.0, 4.0, 6.0));
+
+        let v: nalgebra::Vector2<isize> = Coord2::new(4, -1).into();
+        assert_eq!(Coord2::from(v), Coord2::new(4, -1));
+    }
+}
@@ -0,0 +1,170 @@
+//! Checks for what the solvers assume about their input beyond what it takes
+//! to parse it. They report every problem they find, so that a bad input can
+//! be fixed in one go.
+
+use super::{parse, Coord};
+use crate::{Input, PResult};
+
+/// Where `c` is, in the terms of an editor.
+pub fn at(c: Coord) -> String {
+    format!("line {}, column {}", c.irow() + 1, c.icol() + 1)
+}
+
+/// The problems with a grid of a cell per character: no rows, rows of
+/// different lengths and characters other than those in `allowed`.
+pub fn grid(input: &str, allowed: &str) -> Vec<String> {
+    grid_from(input, allowed, 0)
+}
+
+/// [`grid`] for a grid that starts after `skipped` lines of the input.
+fn grid_from(input: &str, allowed: &str, skipped: usize) -> Vec<String> {
+    let mut lines = input.lines().enumerate().peekable();
+    let Some((_, first)) = lines.peek() else {
+        return vec!["The grid is empty".to_string()];
+    };
+    let cols = first.chars().count();
+
+    let mut problems = vec![];
+    for (r, line) in lines {
+        let r = r + skipped;
+        let len = line.chars().count();
+        if len != cols {
+            problems.push(format!("Line {} has {len} cells, expected {cols}", r + 1));
+        }
+        for (c, ch) in line
+            .chars()
+            .enumerate()
+            .filter(|(_, ch)| !allowed.contains(*ch))
+        {
+            problems.push(format!("Unexpected {ch:?} at {}", at((r, c).into())));
+        }
+    }
+    problems
+}
+
+/// The problems with grids separated by blank lines, each checked like
+/// [`grid`] with its lines numbered as in `input`.
+pub fn grids(input: &str, allowed: &str) -> Vec<String> {
+    let mut problems = vec![];
+    let mut first = 0;
+    for block in input.split("\n\n") {
+        problems.extend(grid_from(block, allowed, first));
+        first += block.lines().count() + 1;
+    }
+    problems
+}
+
+/// The problems `check` has with each line, as `Line N: problem`, or that
+/// there are no lines at all.
+pub fn lines(input: &str, mut check: impl FnMut(&str) -> Vec<String>) -> Vec<String> {
+    if input.trim().is_empty() {
+        return vec!["There are no lines".to_string()];
+    }
+    let mut problems = vec![];
+    for (r, line) in input.lines().enumerate() {
+        for problem in check(line) {
+            problems.push(format!("Line {}: {problem}", r + 1));
+        }
+    }
+    problems
+}
+
+/// What `parser` makes of all of `line`, or where in it and why it fails.
+pub fn parses<O>(
+    line: &str,
+    parser: impl for<'a> FnMut(Input<'a>) -> PResult<'a, O>,
+) -> Result<O, String> {
+    parse::parse_complete(line, parser).map_err(|e| format!("column {}: {}", e.column, e.message))
+}
+
+/// Where `tile` is in a grid, row by row.
+pub fn find(input: &str, tile: char) -> Vec<Coord> {
+    input
+        .lines()
+        .enumerate()
+        .flat_map(|(r, line)| {
+            line.chars()
+                .enumerate()
+                .filter(move |(_, ch)| *ch == tile)
+                .map(move |(c, _)| (r, c).into())
+        })
+        .collect()
+}
+
+/// The only `tile` in a grid, or what's wrong if there are none or several.
+pub fn unique(input: &str, tile: char) -> Result<Coord, String> {
+    match find(input, tile)[..] {
+        [c] => Ok(c),
+        [] => Err(format!("There is no {tile:?}")),
+        ref all => {
+            let places: Vec<String> = all.iter().map(|c| at(*c)).collect();
+            Err(format!(
+                "There are {} {tile:?}s, at {}",
+                all.len(),
+                places.join("; ")
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{find, grid, grids, lines, parses, unique};
+    use crate::common::Coord;
+
+    #[test]
+    fn validate_grid() {
+        assert!(grid("#.\n.#\n", ".#").is_empty());
+        assert_eq!(grid("", ".#"), ["The grid is empty"]);
+        assert_eq!(
+            grid("#.\n.x#\n\n", ".#"),
+            [
+                "Line 2 has 3 cells, expected 2",
+                "Unexpected 'x' at line 2, column 2",
+                "Line 3 has 0 cells, expected 2",
+            ]
+        );
+    }
+
+    #[test]
+    fn validate_grids() {
+        assert!(grids("#.\n.#\n\n.#.\n", ".#").is_empty());
+        assert_eq!(
+            grids("#.\n.#\n\n.x.\n..\n", ".#"),
+            [
+                "Unexpected 'x' at line 4, column 2",
+                "Line 5 has 2 cells, expected 3"
+            ]
+        );
+    }
+
+    #[test]
+    fn validate_lines() {
+        let even = |line: &str| match parses(line, |i| nom::character::complete::u32(i)) {
+            Ok(n) if n % 2 == 1 => vec![format!("{n} is odd")],
+            Ok(_) => vec![],
+            Err(e) => vec![e],
+        };
+        assert!(lines("2\n4", even).is_empty());
+        assert_eq!(
+            lines("2\n3\n4x", even),
+            [
+                "Line 2: 3 is odd",
+                "Line 3: column 2: expected End of file, found 'x'"
+            ]
+        );
+        assert_eq!(lines("\n", even), ["There are no lines"]);
+    }
+
+    #[test]
+    fn validate_tiles() {
+        let input = ".S.\n..S\n...";
+        assert_eq!(find(input, 'S'), [Coord::new(0, 1), Coord::new(1, 2)]);
+        assert_eq!(unique(".S.\n...", 'S'), Ok(Coord::new(0, 1)));
+        assert_eq!(unique("...", 'S'), Err("There is no 'S'".to_string()));
+        assert_eq!(
+            unique(input, 'S'),
+            Err("There are 2 'S's, at line 1, column 2; line 2, column 3".to_string())
+        );
+    }
+}
@@ -1,10 +1,38 @@
-pub fn run() -> anyhow::Result<()> {
-    let input = std::fs::read_to_string("day1.txt")?;
+use crate::common::validate;
 
-    println!("1:1 - {}", run_1(&input)?);
-    println!("1:2 - {}", run_2(&input)?);
+pub struct Day;
 
-    Ok(())
+impl crate::solver::Solver for Day {
+    fn day(&self) -> usize {
+        1
+    }
+
+    fn title(&self) -> &'static str {
+        "Trebuchet?!"
+    }
+
+    fn part1(&self, input: &str) -> anyhow::Result<crate::answer::Answer> {
+        Ok(run_1(input)?.into())
+    }
+
+    fn part2(&self, input: &str) -> anyhow::Result<crate::answer::Answer> {
+        Ok(run_2(input)?.into())
+    }
+
+    fn validate(&self, input: &str) -> Option<Vec<String>> {
+        Some(check_input(input))
+    }
+}
+
+/// Each line has a digit, which both parts need.
+fn check_input(input: &str) -> Vec<String> {
+    validate::lines(input, |line| {
+        if line.contains(|c: char| c.is_ascii_digit()) {
+            vec![]
+        } else {
+            vec!["no digit".to_string()]
+        }
+    })
 }
 
 fn run_1(input: &str) -> anyhow::Result<u32> {
@@ -29,61 +57,45 @@ fn run_1(input: &str) -> anyhow::Result<u32> {
     Ok(sum)
 }
 
-fn get_digit(i: &str) -> Option<u32> {
-    match i {
-        "1" | "one" => Some(1),
-        "2" | "two" => Some(2),
-        "3" | "three" => Some(3),
-        "4" | "four" => Some(4),
-        "5" | "five" => Some(5),
-        "6" | "six" => Some(6),
-        "7" | "seven" => Some(7),
-        "8" | "eight" => Some(8),
-        "9" | "nine" => Some(9),
-        _ => None,
-    }
+/// The digit `s` starts with, either spelled out or as a digit from 1 to 9.
+fn leading_digit(s: &str) -> Option<u32> {
+    const WORDS: [&str; 9] = [
+        "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+    ];
+    (1..=9).find(|n| {
+        s.starts_with(WORDS[*n as usize - 1]) || s.starts_with(char::from(b'0' + *n as u8))
+    })
 }
 
 fn run_2(input: &str) -> anyhow::Result<u32> {
     let mut sum = 0;
     for line in input.lines() {
-        let mut numbers = Vec::new();
-        'start: for start_idx in 0..line.len() {
-            for end_idx in (start_idx + 1)..=line.len() {
-                if let Some(n) = get_digit(&line[start_idx..end_idx]) {
-                    numbers.push(n);
-                    continue 'start;
-                }
-            }
-        }
+        // Spelled out digits can overlap, as in "twone"
+        let numbers: Vec<u32> = line
+            .char_indices()
+            .filter_map(|(i, _)| leading_digit(&line[i..]))
+            .collect();
+        let first = numbers.first().ok_or(anyhow::anyhow!("No digit found"))?;
+        let last = numbers.last().ok_or(anyhow::anyhow!("No digit found"))?;
 
-        sum += numbers[0] * 10 + numbers[numbers.len() - 1];
+        sum += first * 10 + last;
     }
     Ok(sum)
 }
 
 #[cfg(test)]
 mod tests {
-    const INPUT: &str = "1abc2
-pqr3stu8vwx
-a1b2c3d4e5f
-treb7uchet";
-
     #[test]
-    fn day1_run_1() {
-        assert_eq!(super::run_1(INPUT).unwrap(), 142);
+    fn day1_validate() {
+        assert!(super::check_input(&crate::examples::input(1, "calibration")).is_empty());
+        assert_eq!(super::check_input("a1b\ntwo\n"), ["Line 2: no digit"]);
     }
 
-    const INPUT_2: &str = "two1nine
-eightwothree
-abcone2threexyz
-xtwone3four
-4nineeightseven2
-zoneight234
-7pqrstsixteen";
-
     #[test]
     fn day1_run_2() {
-        assert_eq!(super::run_2(INPUT_2).unwrap(), 281);
+        assert_eq!(super::run_2("twone\nx0zero7").unwrap(), 21 + 77);
+        assert_eq!(super::run_2("é1twoñ").unwrap(), 12);
+        let e = super::run_2("one\nabc").unwrap_err();
+        assert_eq!(e.to_string(), "No digit found");
     }
 }
@@ -1,266 +1,148 @@
-use num::Integer;
+use crate::common::{geometry::Polygon, validate, Coord, Dir, Grid};
 
-use crate::common::Coord;
-use std::collections::HashSet;
+pub struct Day;
 
-pub fn run() -> anyhow::Result<()> {
-    let input = std::fs::read_to_string("day10.txt")?;
-
-    println!("10:1 - {}", run_1(&input)?);
-    println!("10:2 - {}", run_2(&input)?);
-
-    Ok(())
-}
-
-fn run_1(input: &str) -> anyhow::Result<usize> {
-    let map: Map = input.lines().map(|row| row.chars().collect()).collect();
-
-    let start: Coord = map
-        .iter()
-        .enumerate()
-        .find_map(|(r, row)| {
-            row.iter()
-                .enumerate()
-                .find(|(_, c)| **c == 'S')
-                .map(|(col, _)| (r, col).into())
-        })
-        .unwrap();
-
-    let mut to_search: Vec<Coord> = Vec::new();
-    let mut visited: HashSet<Coord> = HashSet::new();
+impl crate::solver::Solver for Day {
+    fn day(&self) -> usize {
+        10
+    }
 
-    to_search.push(start);
+    fn title(&self) -> &'static str {
+        "Pipe Maze"
+    }
 
-    while let Some(cur) = to_search.pop() {
-        if visited.contains(&cur) {
-            continue;
-        }
-        visited.insert(cur);
+    fn part1(&self, input: &str) -> anyhow::Result<crate::answer::Answer> {
+        Ok(run_1(input)?.into())
+    }
 
-        let nbrs = cur
-            .neighbors()
-            .filter(|coord| !visited.contains(coord) && can_go(&cur, coord, &map));
+    fn part2(&self, input: &str) -> anyhow::Result<crate::answer::Answer> {
+        Ok(run_2(input)?.into())
+    }
 
-        to_search.extend(nbrs);
+    fn validate(&self, input: &str) -> Option<Vec<String>> {
+        Some(check_input(input))
     }
+}
 
-    Ok(visited.len() / 2)
+fn run_1(input: &str) -> anyhow::Result<usize> {
+    let map: Map = input.parse()?;
+    Ok(find_loop(&map)?.len() / 2)
 }
 
 fn run_2(input: &str) -> anyhow::Result<usize> {
-    let map: Map = input.lines().map(|row| row.chars().collect()).collect();
-
-    let start: Coord = map
-        .iter()
-        .enumerate()
-        .find_map(|(r, row)| {
-            row.iter()
-                .enumerate()
-                .find(|(_, c)| **c == 'S')
-                .map(|(col, _)| (r, col).into())
-        })
-        .unwrap();
-
-    let mut to_search: Vec<Coord> = Vec::new();
-    let mut visited: HashSet<Coord> = HashSet::new();
-
-    to_search.push(start);
+    let map: Map = input.parse()?;
+    let pipe = Polygon::new(find_loop(&map)?);
 
-    while let Some(cur) = to_search.pop() {
-        if visited.contains(&cur) {
-            continue;
+    if crate::debug_enabled() {
+        let mut marked = map.clone();
+        for coord in map.coords().filter(|c| pipe.contains(*c)) {
+            marked[coord] = 'I';
         }
-        visited.insert(cur);
-
-        let nbrs = cur
-            .neighbors()
-            .filter(|coord| !visited.contains(coord) && can_go(&cur, coord, &map));
-
-        to_search.extend(nbrs);
+        debug!("{marked}");
     }
 
-    for (r, row) in map.iter().enumerate() {
-        for (col, c) in row.iter().enumerate() {
-            let coord: Coord = (r, col).into();
-            if visited.contains(&coord) {
-                print!("{c}");
-            } else {
-                print!(".");
+    // Tiles are points, so the tiles enclosed are the points inside
+    Ok(pipe.interior_points() as usize)
+}
+
+/// One start tile, and nothing but pipes and ground around it. The start
+/// has to connect to exactly two pipes to be on a single loop.
+fn check_input(input: &str) -> Vec<String> {
+    let mut problems = validate::grid(input, "|-LJ7F.S");
+    match validate::unique(input, 'S') {
+        Ok(start) => {
+            if let Ok(map) = input.parse::<Map>() {
+                let pipes = map
+                    .neighbors(start)
+                    .filter(|n| can_go(&start, n, &map))
+                    .count();
+                if pipes != 2 {
+                    problems.push(format!(
+                        "The start tile connects to {pipes} pipes, expected 2"
+                    ));
+                }
             }
         }
-        println!();
+        Err(e) => problems.push(e),
     }
+    problems
+}
 
-    // let mut inside_cnt = 0;
-    let mut per_row_count = HashSet::new();
-    for (r, row) in map.iter().enumerate() {
-        let mut hit_cnt = 0;
-        // let last_col = row.len() -  row.iter().rev().position(|c| *c != '.').unwrap_or(row.len());
-        let last_col = row.len();
-        println!("r: {r} - {last_col}");
-        for c in 0..last_col {
-            let map_item = map[r][c];
-            let cur: Coord = (r, c).into();
-
-            // println!("{cur} -> {hit_cnt}");
-            match map_item {
-                '|' | 'F' | 'S' | 'L' | 'J' | '7' => {
-                    hit_cnt += 1;
-                }
-                '.' if hit_cnt.is_odd() => {
-                    println!("Adding {cur}");
-                    per_row_count.insert(cur);
-                }
-                _ => (),
+/// The tiles of the loop through the start tile, in order.
+fn find_loop(map: &Map) -> anyhow::Result<Vec<Coord>> {
+    let start = map
+        .position(|c| *c == 'S')
+        .ok_or_else(|| anyhow::anyhow!("No start tile 'S' in the map"))?;
+
+    // Pipes only connect to two others, so each way out of the start either
+    // comes back to it or ends
+    for first in map.neighbors(start).filter(|n| can_go(&start, n, map)) {
+        let mut path = vec![start, first];
+        let (mut prev, mut cur) = (start, first);
+        while let Some(next) = map
+            .neighbors(cur)
+            .find(|n| *n != prev && can_go(&cur, n, map))
+        {
+            if next == start {
+                return Ok(path);
             }
-            // println!("{cur} <- {hit_cnt}");
+            path.push(next);
+            (prev, cur) = (cur, next);
         }
-        println!("r: {r} hit_cnt: {hit_cnt}");
     }
-
-    // let mut per_col_count = HashSet::new();
-    // for c in 0..map[0].len() {
-    //     let mut hit_cnt = 0;
-    //     for (r, row) in map.iter().enumerate() {
-    //         let map_item = row[c];
-    //         let cur: Coord = (r, c).into();
-
-    //         // println!("{cur} -> {hit_cnt}");
-    //         match map_item {
-    //             '-' | 'F' | 'S' | 'L' | '7' | 'J' => {
-    //                 hit_cnt += 1;
-    //             }
-    //             '.' if hit_cnt.is_odd() => {
-    //                 println!("Adding {cur}");
-    //                 per_col_count.insert(cur);
-    //             }
-    //             _ => (),
-    //         }
-    //         // println!("{cur} <- {hit_cnt}");
-    //     }
-    // }
-    println!("per row: {}", per_row_count.len());
-    // println!("per col: {}", per_col_count.len());
-
-    // Ok(per_row_count.intersection(&per_col_count).count())
-    Ok(per_row_count.len())
+    anyhow::bail!("No loop through the start tile")
 }
 
-type Map = Vec<Vec<char>>;
+type Map = Grid<char>;
 
 fn can_go(from: &Coord, to: &Coord, map: &Map) -> bool {
-    if map
-        .get(to.row())
-        .and_then(|row| row.get(to.col()))
-        .is_none()
-    {
+    let (Some(&src), Some(&dst)) = (map.get(*from), map.get(*to)) else {
         return false;
-    }
-    let src = map[from.row()][from.col()];
-    match (to.irow() - from.irow(), to.icol() - from.icol()) {
-        // Coming from north
-        (1, 0) => {
-            (src == '|' || src == '7' || src == 'F' || src == 'S')
-                && matches!(map[to.row()][to.col()], '|' | 'L' | 'J')
-        }
-        // Coming from south
-        (-1, 0) => {
-            (src == '|' || src == 'L' || src == 'J' || src == 'S')
-                && matches!(map[to.row()][to.col()], '|' | '7' | 'F')
-        }
-        // coming from west
-        (0, 1) => {
-            (src == '-' || src == 'L' || src == 'F' || src == 'S')
-                && matches!(map[to.row()][to.col()], '-' | 'J' | '7')
-        }
-        // coming from east
-        (0, -1) => {
-            (src == '-' || src == 'J' || src == '7' || src == 'S')
-                && matches!(map[to.row()][to.col()], '-' | 'L' | 'F')
-        }
-        _ => unreachable!(),
+    };
+    Dir::CARDINAL
+        .into_iter()
+        .find(|d| *from + d.movement() == *to)
+        .is_some_and(|d| connections(src).contains(&d) && connections(dst).contains(&d.reverse()))
+}
+
+/// The directions a pipe tile opens to.
+fn connections(tile: char) -> &'static [Dir] {
+    match tile {
+        '|' => &[Dir::N, Dir::S],
+        '-' => &[Dir::E, Dir::W],
+        'L' => &[Dir::N, Dir::E],
+        'J' => &[Dir::N, Dir::W],
+        '7' => &[Dir::S, Dir::W],
+        'F' => &[Dir::S, Dir::E],
+        'S' => &Dir::CARDINAL,
+        _ => &[],
     }
 }
 
 #[cfg(test)]
 mod tests {
-    const INPUT_1: &str = ".....
-.S-7.
-.|.|.
-.L-J.
-.....";
-    const INPUT_2: &str = "..F7.
-.FJ|.
-SJ.L7
-|F--J
-LJ...";
-
     #[test]
-    fn day10_run_1() {
-        assert_eq!(super::run_1(INPUT_1).unwrap(), 4);
-        assert_eq!(super::run_1(INPUT_2).unwrap(), 8);
-    }
-
-    #[test]
-    #[ignore]
-    fn day10_run_2() {
-        // assert_eq!(super::run_2(".S.").unwrap(), 1);
-        // assert_eq!(super::run_2(".S-.").unwrap(), 1);
-        // assert_eq!(
-        //     super::run_2(
-        //         ".F7.
-        // .S|."
-        //     )
-        //     .unwrap(),
-        //     0
-        // );
-        assert_eq!(
-            super::run_2(
-                "...........
-.S-------7.
-.|F-----7|.
-.||.....||.
-.||.....||.
-.|L-7.F-J|.
-.|..|.|..|.
-.L--J.L--J.
-..........."
-            )
-            .unwrap(),
-            4
-        );
+    fn day10_validate() {
+        for name in [
+            "square_loop",
+            "complex_loop",
+            "enclosed",
+            "larger",
+            "junk_pipes",
+        ] {
+            let input = crate::examples::input(10, name);
+            assert!(super::check_input(&input).is_empty(), "{name}");
+        }
         assert_eq!(
-            super::run_2(
-                ".F----7F7F7F7F-7....
-.|F--7||||||||FJ....
-.||.FJ||||||||L7....
-FJL7L7LJLJ||LJ.L-7..
-L--J.L7...LJS7F-7L7.
-....F-J..F7FJ|L7L7L7
-....L7.F7||L7|.L7L7|
-.....|FJLJ|FJ|F7|.LJ
-....FJL-7.||.||||...
-....L---J.LJ.LJLJ..."
-            )
-            .unwrap(),
-            8
+            super::check_input(".S-7.\n.|.x.\n.L-S."),
+            [
+                "Unexpected 'x' at line 2, column 4",
+                "There are 2 'S's, at line 1, column 2; line 3, column 4",
+            ]
         );
-
         assert_eq!(
-            super::run_2(
-                "FF7FSF7F7F7F7F7F---7
-L|LJ||||||||||||F--J
-FL-7LJLJ||||||LJL-77
-F--JF--7||LJLJ7F7FJ-
-L---JF-JLJ.||-FJLJJ7
-|F|F-JF---7F7-L7L|7|
-|FFJF7L7F-JF7|JL---7
-7-L-JL7||F7|L7F-7F7|
-L.L7LFJ|||||FJL7||LJ
-L7JLJL-JLJLJL--JLJ.L"
-            )
-            .unwrap(),
-            10
+            super::check_input("S-7\n..|\n|-J"),
+            ["The start tile connects to 1 pipes, expected 2"]
         );
     }
 }
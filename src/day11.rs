@@ -1,12 +1,44 @@
-use crate::common::Coord;
+use crate::common::{validate, Coord};
 
-pub fn run() -> anyhow::Result<()> {
-    let input = std::fs::read_to_string("day11.txt")?;
+#[derive(serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Day {
+    /// How many times larger an empty row or column becomes in part 2
+    pub expansion: isize,
+}
+
+impl Day {
+    pub const DEFAULT: Self = Self {
+        expansion: 1_000_000,
+    };
+}
+
+impl Default for Day {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl crate::solver::Solver for Day {
+    fn day(&self) -> usize {
+        11
+    }
+
+    fn title(&self) -> &'static str {
+        "Cosmic Expansion"
+    }
+
+    fn part1(&self, input: &str) -> anyhow::Result<crate::answer::Answer> {
+        Ok(run_1(input)?.into())
+    }
 
-    println!("11:1 - {}", run_1(&input)?);
-    println!("11:2 - {}", run_2(&input, 1_000_000)?);
+    fn part2(&self, input: &str) -> anyhow::Result<crate::answer::Answer> {
+        Ok(run_2(input, self.expansion)?.into())
+    }
 
-    Ok(())
+    fn validate(&self, input: &str) -> Option<Vec<String>> {
+        Some(check_input(input))
+    }
 }
 
 fn run_expand(input: &str, expansion: isize) -> anyhow::Result<usize> {
@@ -71,6 +103,11 @@ fn run_expand(input: &str, expansion: isize) -> anyhow::Result<usize> {
     Ok(dist)
 }
 
+/// A grid of empty space and galaxies.
+fn check_input(input: &str) -> Vec<String> {
+    validate::grid(input, ".#")
+}
+
 fn run_1(input: &str) -> anyhow::Result<usize> {
     run_expand(input, 2)
 }
@@ -78,28 +115,3 @@ fn run_1(input: &str) -> anyhow::Result<usize> {
 fn run_2(input: &str, expansion: isize) -> anyhow::Result<usize> {
     run_expand(input, expansion)
 }
-
-#[cfg(test)]
-mod tests {
-    const INPUT: &str = "...#......
-.......#..
-#.........
-..........
-......#...
-.#........
-.........#
-..........
-.......#..
-#...#.....";
-
-    #[test]
-    fn day11_run_1() {
-        assert_eq!(super::run_1(INPUT).unwrap(), 374);
-    }
-
-    #[test]
-    fn day11_run_2() {
-        assert_eq!(super::run_2(INPUT, 10).unwrap(), 1030);
-        assert_eq!(super::run_2(INPUT, 100).unwrap(), 8410);
-    }
-}
@@ -1,12 +1,29 @@
 use rayon::prelude::*;
 
-pub fn run() -> anyhow::Result<()> {
-    let input = std::fs::read_to_string("day12.txt")?;
+use crate::common::parse;
 
-    println!("12:1 - {}", run_1(&input)?);
-    println!("12:2 - {}", run_2(&input)?);
+pub struct Day;
 
-    Ok(())
+impl crate::solver::Solver for Day {
+    fn day(&self) -> usize {
+        12
+    }
+
+    fn title(&self) -> &'static str {
+        "Hot Springs"
+    }
+
+    fn part1(&self, input: &str) -> anyhow::Result<crate::answer::Answer> {
+        Ok(run_1(input)?.into())
+    }
+
+    fn part2(&self, input: &str) -> anyhow::Result<crate::answer::Answer> {
+        Ok(run_2(input)?.into())
+    }
+
+    fn validate(&self, input: &str) -> Option<Vec<String>> {
+        Some(check_input(input))
+    }
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -48,7 +65,7 @@ fn can_be_damaged(s1: &[Spring], len: usize) -> bool {
 fn arrangements(springs: &[Spring], damaged: &[usize]) -> usize {
     if damaged.is_empty() {
         //println!("No more damage groups");
-        if !springs.iter().any(|s| *s == Spring::Damaged) {
+        if !springs.contains(&Spring::Damaged) {
             return 1;
         } else {
             return 0;
@@ -115,7 +132,10 @@ fn parse(i: crate::Input) -> crate::PResult<Vec<Row>> {
         )(i)
     }
 
-    let (i, rows) = nom::multi::separated_list1(nom::character::complete::newline, parse_rows)(i)?;
+    let (i, rows) = nom::multi::separated_list1(
+        nom::character::complete::newline,
+        nom::error::context("row", parse_rows),
+    )(i)?;
     Ok((
         i,
         rows.into_iter()
@@ -127,9 +147,38 @@ fn parse(i: crate::Input) -> crate::PResult<Vec<Row>> {
     ))
 }
 
+/// Each row is springs that are `.`, `#` or `?`, a space and the sizes of
+/// the damaged groups, none of them 0.
+fn check_input(input: &str) -> Vec<String> {
+    let mut problems = vec![];
+    if input.trim().is_empty() {
+        problems.push("There are no rows".to_string());
+    }
+    for (row, line) in input.lines().enumerate() {
+        let Some((springs, groups)) = line.split_once(' ') else {
+            problems.push(format!(
+                "Line {}: expected springs and group sizes",
+                row + 1
+            ));
+            continue;
+        };
+        if springs.is_empty() {
+            problems.push(format!("Line {}: no springs", row + 1));
+        }
+        for c in springs.chars().filter(|c| !".#?".contains(*c)) {
+            problems.push(format!("Line {}: unexpected spring {c:?}", row + 1));
+        }
+        for group in groups.split(',') {
+            if !group.parse::<usize>().is_ok_and(|g| g > 0) {
+                problems.push(format!("Line {}: invalid group size {group:?}", row + 1));
+            }
+        }
+    }
+    problems
+}
+
 fn run_1(input: &str) -> anyhow::Result<usize> {
-    let (i, rows) = parse(input).map_err(|e| anyhow::anyhow!("{e}"))?;
-    assert!(i.is_empty());
+    let rows = parse::parse_complete(input, parse)?;
 
     let mut results = Vec::with_capacity(rows.len());
     for row in rows.into_iter() {
@@ -139,14 +188,13 @@ fn run_1(input: &str) -> anyhow::Result<usize> {
 }
 
 fn run_2(input: &str) -> anyhow::Result<usize> {
-    let (i, rows) = parse(input).map_err(|e| anyhow::anyhow!("{e}"))?;
-    assert!(i.is_empty());
+    let rows = parse::parse_complete(input, parse)?;
 
     let results: Vec<usize> = rows
         .par_iter()
         .map(|row| {
             let unfolded = unfold(row);
-        println!("row");
+            debug!("row");
             arrangements(&unfolded.springs, &unfolded.dmg_groups)
         })
         .collect();
@@ -176,23 +224,6 @@ fn unfold(r: &Row) -> Row {
 
 #[cfg(test)]
 mod tests {
-    const INPUT: &str = "???.### 1,1,3
-.??..??...?##. 1,1,3
-?#?#?#?#?#?#?#? 1,3,1,6
-????.#...#... 4,1,1
-????.######..#####. 1,6,5
-?###???????? 3,2,1";
-
-    #[test]
-    fn day12_run_1() {
-        assert_eq!(super::run_1(INPUT).unwrap(), 21);
-    }
-
-    #[test]
-    fn day12_run_2() {
-        assert_eq!(super::run_2(INPUT).unwrap(), 525152);
-    }
-
     #[test]
     fn day12_arrangements() {
         let (_, rows) = super::parse("# 1").unwrap();
@@ -240,4 +271,20 @@ mod tests {
         let r1 = super::unfold(&r1[0]);
         assert_eq!(r1, r2[0]);
     }
+
+    #[test]
+    fn day12_validate() {
+        assert!(super::check_input(&crate::examples::input(12, "example")).is_empty());
+        assert_eq!(
+            super::check_input("#.x? 1,0\n.#\n 1,a"),
+            [
+                "Line 1: unexpected spring 'x'",
+                "Line 1: invalid group size \"0\"",
+                "Line 2: expected springs and group sizes",
+                "Line 3: no springs",
+                "Line 3: invalid group size \"a\"",
+            ]
+        );
+        assert_eq!(super::check_input(""), ["There are no rows"]);
+    }
 }
@@ -1,70 +1,65 @@
-pub fn run() -> anyhow::Result<()> {
-    let input = std::fs::read_to_string("day13.txt")?;
+use crate::common::{parse, validate, Grid};
 
-    println!("13:1 - {}", run_1(&input)?);
-    println!("13:2 - {}", run_2(&input)?);
+pub struct Day;
 
-    Ok(())
+impl crate::solver::Solver for Day {
+    fn day(&self) -> usize {
+        13
+    }
+
+    fn title(&self) -> &'static str {
+        "Point of Incidence"
+    }
+
+    fn part1(&self, input: &str) -> anyhow::Result<crate::answer::Answer> {
+        Ok(run_1(input)?.into())
+    }
+
+    fn part2(&self, input: &str) -> anyhow::Result<crate::answer::Answer> {
+        Ok(run_2(input)?.into())
+    }
+
+    fn validate(&self, input: &str) -> Option<Vec<String>> {
+        Some(check_input(input))
+    }
+}
+
+fn parse(input: &str) -> anyhow::Result<Vec<Grid<char>>> {
+    let patterns = parse::parse_complete(input, |i| {
+        let pattern = parse::grid(|c| matches!(c, '.' | '#').then_some(c));
+        nom::error::context("pattern", parse::blocks(pattern))(i)
+    })?;
+    Ok(patterns)
 }
 
 // 47527 -- too high
 // 47527 -- too high
 // 20074 -- too low
 // 29463 -- wrong
-fn run_1(input: &str) -> anyhow::Result<usize> {
-    //let mut maps: Vec<ndarray::ArrayD<usize>> = Vec::new();
-    let mut row_maps = Vec::new();
-    let mut col_maps: Vec<Vec<usize>> = Vec::new();
-    row_maps.push(Vec::new());
-    col_maps.push(Vec::new());
-    //maps.push(ndarray::ArrayD::default(ndarray::IxDyn(&[0, 0])));
-    //let a_zero: ndarray::ArrayD<usize> = ndarray::ArrayD::zeros(ndarray::IxDyn(&[1]));
-    //let a_one : ndarray::ArrayD<usize> = ndarray::ArrayD::ones(ndarray::IxDyn(&[1]));
-    for line in input.lines() {
-        if line.is_empty() {
-            //maps.push(ndarray::ArrayD::default(ndarray::IxDyn(&[0, 0])));
-            row_maps.push(Vec::new());
-            col_maps.push(Vec::new());
-            continue;
-        }
-        //let ml = maps.len();
-        //let mut cur_map = &mut maps[ml - 1];
-        let mut val = 0;
-        //let mut cur_row = ndarray::ArrayD::default(ndarray::IxDyn(&[1]));
-        for (col, c) in line.chars().enumerate() {
-            if col_maps[col_maps.len() - 1].get(col).is_none() {
-                let l = col_maps.len();
-                col_maps[l - 1].push(0);
-            }
-
-            if c == '#' {
-                //dbg!{&cur_row};
-                //cur_row.push(ndarray::Axis(0), a_one.view())
-                //    .context("add one")?;
-                val = (val << 1) + 1;
-                let l = col_maps.len();
-                let c_val = &mut col_maps[l - 1][col];
-                *c_val = (*c_val << 1) + 1;
-            } else {
-                //dbg!{&cur_row};
-                //cur_row.push(ndarray::Axis(0), a_zero.view())?;
-                val <<= 1;
-                let l = col_maps.len();
-                let c_val = &mut col_maps[l - 1][col];
-                *c_val <<= 1;
-            }
-        }
-
-        let l = row_maps.len();
-        row_maps[l - 1].push(val);
-    }
+/// Patterns of ash and rocks, separated by blank lines.
+fn check_input(input: &str) -> Vec<String> {
+    validate::grids(input, ".#")
+}
 
-    assert_eq!(row_maps.len(), col_maps.len());
+fn run_1(input: &str) -> anyhow::Result<usize> {
+    let maps = parse(input)?;
+    // Each row and column as a number, with a bit set per rock
+    let bits = |cells: &mut dyn Iterator<Item = &char>| {
+        cells.fold(0usize, |val, c| (val << 1) + usize::from(*c == '#'))
+    };
+    let row_maps: Vec<Vec<usize>> = maps
+        .iter()
+        .map(|m| m.iter_rows().map(|row| bits(&mut row.iter())).collect())
+        .collect();
+    let col_maps: Vec<Vec<usize>> = maps
+        .iter()
+        .map(|m| m.iter_cols().map(|mut col| bits(&mut col)).collect())
+        .collect();
 
     let mut res = 0;
     let s = 0;
     for map in col_maps.iter().skip(s) {
-        dbg! {map};
+        debug!("{map:?}");
         for check_row in 1..(map.len() - 1) {
             let mut above_row = check_row;
             let mut below_row = check_row + 1;
@@ -78,7 +73,7 @@ fn run_1(input: &str) -> anyhow::Result<usize> {
                     }
                     _ => {
                         // we've come to an end
-                        println!("Found col {check_row}");
+                        debug!("Found col {check_row}");
                         res += check_row + 1;
                         break;
                     }
@@ -90,7 +85,7 @@ fn run_1(input: &str) -> anyhow::Result<usize> {
     }
 
     for map in row_maps.iter().skip(s) {
-        dbg! {map};
+        debug!("{map:?}");
         for check_row in 1..(map.len() - 1) {
             let mut above_row = check_row;
             let mut below_row = check_row + 1;
@@ -104,7 +99,7 @@ fn run_1(input: &str) -> anyhow::Result<usize> {
                     }
                     _ => {
                         // we've come to an end
-                        println!("Found row {check_row}");
+                        debug!("Found row {check_row}");
                         res += 100 * (check_row + 1);
                         break;
                     }
@@ -119,32 +114,5 @@ fn run_1(input: &str) -> anyhow::Result<usize> {
 }
 
 fn run_2(_input: &str) -> anyhow::Result<usize> {
-    todo!()
-}
-
-#[cfg(test)]
-mod tests {
-    const INPUT: &str = "#.##..##.
-..#.##.#.
-##......#
-##......#
-..#.##.#.
-..##..##.
-#.#.##.#.
-
-#...##..#
-#....#..#
-..##..###
-#####.##.
-#####.##.
-..##..###
-#....#..#";
-
-    #[test]
-    fn day13_run_1() {
-        assert_eq!(super::run_1(INPUT).unwrap(), 405);
-    }
-
-    #[test]
-    fn day13_run_2() {}
+    Err(crate::solver::NotImplemented.into())
 }
@@ -1,10 +1,27 @@
-pub fn run() -> anyhow::Result<()> {
-    let input = std::fs::read_to_string("day14.txt")?;
+use crate::common::{cycle, validate, Coord, Grid};
 
-    println!("14:1 - {}", run_1(&input)?);
-    println!("14:2 - {}", run_2(&input)?);
+pub struct Day;
 
-    Ok(())
+impl crate::solver::Solver for Day {
+    fn day(&self) -> usize {
+        14
+    }
+
+    fn title(&self) -> &'static str {
+        "Parabolic Reflector Dish"
+    }
+
+    fn part1(&self, input: &str) -> anyhow::Result<crate::answer::Answer> {
+        Ok(run_1(input)?.into())
+    }
+
+    fn part2(&self, input: &str) -> anyhow::Result<crate::answer::Answer> {
+        Ok(run_2(input)?.into())
+    }
+
+    fn validate(&self, input: &str) -> Option<Vec<String>> {
+        Some(check_input(input))
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Hash)]
@@ -26,31 +43,49 @@ enum Rock {
 //     });
 // }
 
-fn run_1(input: &str) -> anyhow::Result<usize> {
-    let board: Vec<Vec<Option<Rock>>> = input
-        .lines()
-        .map(|line| {
-            line.chars()
-                .map(|c| match c {
-                    '#' => Some(Rock::Cubic),
-                    'O' => Some(Rock::Rounded),
-                    _ => None,
-                })
-                .collect()
+type Board = Grid<Option<Rock>>;
+
+fn parse(input: &str) -> anyhow::Result<Board> {
+    Grid::parse(input, |c| {
+        Ok(match c {
+            '#' => Some(Rock::Cubic),
+            'O' => Some(Rock::Rounded),
+            _ => None,
         })
-        .collect();
+    })
+}
+
+fn at(row: usize, col: usize) -> Coord {
+    (row, col).into()
+}
+
+fn north_load(board: &Board) -> usize {
+    board
+        .iter_rows()
+        .rev()
+        .enumerate()
+        .map(|(r_num, row)| row.iter().filter(|o| *o == &Some(Rock::Rounded)).count() * (r_num + 1))
+        .sum()
+}
+
+/// A grid of empty space, cube rocks and round rocks.
+fn check_input(input: &str) -> Vec<String> {
+    validate::grid(input, ".#O")
+}
+
+fn run_1(input: &str) -> anyhow::Result<usize> {
+    let board = parse(input)?;
 
     let mut new_board = board.clone();
-    for cur_row in 0..board.len() {
-        let borrowed_row = new_board[cur_row].clone();
-        for (c, o) in borrowed_row.iter().enumerate() {
+    for cur_row in 0..board.rows() {
+        for c in 0..board.cols() {
             // Check below to find ball
-            if o.is_none() {
-                for row in (cur_row + 1)..board.len() {
-                    if Some(Rock::Rounded) == new_board[row][c] {
-                        new_board[cur_row][c] = new_board[row][c].take();
+            if new_board[at(cur_row, c)].is_none() {
+                for row in (cur_row + 1)..board.rows() {
+                    if Some(Rock::Rounded) == new_board[at(row, c)] {
+                        new_board[at(cur_row, c)] = new_board[at(row, c)].take();
                         break;
-                    } else if Some(Rock::Cubic) == board[row][c] {
+                    } else if Some(Rock::Cubic) == board[at(row, c)] {
                         break;
                     }
                 }
@@ -58,26 +93,20 @@ fn run_1(input: &str) -> anyhow::Result<usize> {
         }
     }
 
-    Ok(new_board
-        .iter()
-        .rev()
-        .enumerate()
-        .map(|(r_num, row)| row.iter().filter(|o| *o == &Some(Rock::Rounded)).count() * (r_num + 1))
-        .sum())
+    Ok(north_load(&new_board))
 }
 
-fn run_cycle(board: &mut [Vec<Option<Rock>>]) {
+fn run_cycle(board: &mut Board) {
     // North
-    for cur_row in 0..board.len() {
-        let borrowed_row = board[cur_row].clone();
-        for (c, o) in borrowed_row.iter().enumerate() {
+    for cur_row in 0..board.rows() {
+        for c in 0..board.cols() {
             // Check below to find ball
-            if o.is_none() {
-                for row in (cur_row + 1)..board.len() {
-                    if Some(Rock::Rounded) == board[row][c] {
-                        board[cur_row][c] = board[row][c].take();
+            if board[at(cur_row, c)].is_none() {
+                for row in (cur_row + 1)..board.rows() {
+                    if Some(Rock::Rounded) == board[at(row, c)] {
+                        board[at(cur_row, c)] = board[at(row, c)].take();
                         break;
-                    } else if Some(Rock::Cubic) == board[row][c] {
+                    } else if Some(Rock::Cubic) == board[at(row, c)] {
                         break;
                     }
                 }
@@ -88,7 +117,7 @@ fn run_cycle(board: &mut [Vec<Option<Rock>>]) {
     // print_board(&board);
 
     // West
-    for cur_row in &mut *board {
+    for cur_row in board.iter_rows_mut() {
         // Check east to find ball
         for col in 0..(cur_row.len() - 1) {
             let o = &cur_row[col];
@@ -108,16 +137,15 @@ fn run_cycle(board: &mut [Vec<Option<Rock>>]) {
     // print_board(&board);
 
     // South
-    for cur_row in (1..board.len()).rev() {
-        let borrowed_row = board[cur_row].clone();
-        for (c, o) in borrowed_row.iter().enumerate() {
+    for cur_row in (1..board.rows()).rev() {
+        for c in 0..board.cols() {
             // Check above to find ball
-            if o.is_none() {
+            if board[at(cur_row, c)].is_none() {
                 for row in (0..cur_row).rev() {
-                    if Some(Rock::Rounded) == board[row][c] {
-                        board[cur_row][c] = board[row][c].take();
+                    if Some(Rock::Rounded) == board[at(row, c)] {
+                        board[at(cur_row, c)] = board[at(row, c)].take();
                         break;
-                    } else if Some(Rock::Cubic) == board[row][c] {
+                    } else if Some(Rock::Cubic) == board[at(row, c)] {
                         break;
                     }
                 }
@@ -129,7 +157,7 @@ fn run_cycle(board: &mut [Vec<Option<Rock>>]) {
     // print_board(&board);
 
     // East
-    for cur_row in &mut * board {
+    for cur_row in board.iter_rows_mut() {
         // Check east to find ball
         for col in (1..(cur_row.len())).rev() {
             let o = &cur_row[col];
@@ -154,61 +182,17 @@ fn run_cycle(board: &mut [Vec<Option<Rock>>]) {
 // too low: 93694
 
 fn run_2(input: &str) -> anyhow::Result<usize> {
-    let mut board: Vec<Vec<Option<Rock>>> = input
-        .lines()
-        .map(|line| {
-            line.chars()
-                .map(|c| match c {
-                    '#' => Some(Rock::Cubic),
-                    'O' => Some(Rock::Rounded),
-                    _ => None,
-                })
-                .collect()
-        })
-        .collect();
-
-    let mut prev = vec![];
-    let dest = 1_000_000_000;
-    for i in 1..=dest {
-        run_cycle(&mut board);
-        if !prev.contains(&board) {
-            prev.push(board.clone());
-        } else if (i % prev.len()) == (dest % prev.len()) {
-            println!("Break {i}");
-            println!("m: {}", dest % prev.len());
-            break;
-        }
-    }
-
-    Ok(board
-        .iter()
-        .rev()
-        .enumerate()
-        .map(|(r_num, row)| row.iter().filter(|o| *o == &Some(Rock::Rounded)).count() * (r_num + 1))
-        .sum())
-}
-
-#[cfg(test)]
-mod tests {
-    const INPUT: &str = "O....#....
-O.OO#....#
-.....##...
-OO.#O....O
-.O.....O#.
-O.#..O.#.#
-..O..#O..O
-.......O..
-#....###..
-#OO..#....";
-
-    #[test]
-    fn day14_run_1() {
-        assert_eq!(super::run_1(INPUT).unwrap(), 136);
-    }
-
-    #[test]
-    #[ignore]
-    fn day14_run_2() {
-        assert_eq!(super::run_2(INPUT).unwrap(), 64);
-    }
+    let board = parse(input)?;
+
+    let board = cycle::nth(
+        board,
+        |board| {
+            let mut board = board.clone();
+            run_cycle(&mut board);
+            board
+        },
+        1_000_000_000,
+    );
+
+    Ok(north_load(&board))
 }
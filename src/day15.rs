@@ -1,55 +1,88 @@
 use nom::InputIter;
 
-pub fn run() -> anyhow::Result<()> {
-    let input = std::fs::read_to_string("day15.txt")?;
+pub struct Day;
 
-    println!("15:1 - {}", run_1(&input)?);
-    println!("15:2 - {}", run_2(&input)?);
+impl crate::solver::Solver for Day {
+    fn day(&self) -> usize {
+        15
+    }
+
+    fn title(&self) -> &'static str {
+        "Lens Library"
+    }
+
+    fn part1(&self, input: &str) -> anyhow::Result<crate::answer::Answer> {
+        Ok(run_1(input)?.into())
+    }
+
+    fn part2(&self, input: &str) -> anyhow::Result<crate::answer::Answer> {
+        Ok(run_2(input)?.into())
+    }
 
-    Ok(())
+    fn validate(&self, input: &str) -> Option<Vec<String>> {
+        Some(check_input(input))
+    }
 }
 
 fn hash(i: &str) -> usize {
     i.chars().fold(0, |p, c| (17 * (p + (c as usize))) % 256)
 }
 
-fn run_1(input: &str) -> anyhow::Result<usize> {
-    Ok(input
+fn first_line(input: &str) -> anyhow::Result<&str> {
+    input
         .lines()
         .next()
-        .unwrap()
-        .split(',')
-        .map(hash)
-        .sum())
+        .ok_or_else(|| anyhow::anyhow!("Empty input"))
+}
+
+/// A single line of steps, each a label of letters with `-` or `=` and a
+/// focal length from 1 to 9.
+fn check_input(input: &str) -> Vec<String> {
+    let mut problems = vec![];
+    let mut lines = input.lines();
+    for (n, step) in lines.next().unwrap_or_default().split(',').enumerate() {
+        let label = step.trim_end_matches(|c: char| c.is_ascii_digit() || "-=".contains(c));
+        let op = &step[label.len()..];
+        let valid_op = op == "-"
+            || op
+                .strip_prefix('=')
+                .is_some_and(|f| f.len() == 1 && f != "0");
+        if label.is_empty() || !label.chars().all(|c| c.is_ascii_lowercase()) || !valid_op {
+            problems.push(format!("Step {}: invalid step {step:?}", n + 1));
+        }
+    }
+    if lines.any(|line| !line.is_empty()) {
+        problems.push("The steps are on more than one line".to_string());
+    }
+    problems
+}
+
+fn run_1(input: &str) -> anyhow::Result<usize> {
+    Ok(first_line(input)?.split(',').map(hash).sum())
 }
 
 fn run_2(input: &str) -> anyhow::Result<usize> {
-    const ARRAY_REPEAT_VALUE: Vec<&str> = Vec::new();
+    const ARRAY_REPEAT_VALUE: Vec<(&str, usize)> = Vec::new();
 
     let mut boxes = [ARRAY_REPEAT_VALUE; 256];
-    input
-        .lines()
-        .next()
-        .unwrap()
-        .split(',')
-        .for_each(|i| {
-            if let Some(e) = i.position(|c| c == '=') {
-                let label = &i[..e];
-                // let d = &i[(e+1)..];
-                let h = hash(label);
-                if let Some(v) = boxes[h].iter_mut().find(|v| v.starts_with(label)) {
-                    *v = i;
-                } else {
-                    boxes[h].push(i);
-                }
-            } else if let Some(d) = i.position(|c| c == '-') {
-                let label = &i[..d];
-                let h = hash(label);
-                boxes[h].retain(|v| !v.starts_with(label));
+    for (step, i) in first_line(input)?.split(',').enumerate() {
+        if let Some(e) = i.position(|c| c == '=') {
+            let label = &i[..e];
+            let focal_len = i[(e + 1)..].parse::<usize>().map_err(|err| {
+                anyhow::anyhow!("Invalid focal length in step {} '{i}': {err}", step + 1)
+            })?;
+            let h = hash(label);
+            if let Some(v) = boxes[h].iter_mut().find(|(v, _)| v.starts_with(label)) {
+                *v = (i, focal_len);
+            } else {
+                boxes[h].push((i, focal_len));
             }
-        });
-
-    let focal_len = |i: &str| i.split('=').nth(1).unwrap().parse::<usize>().unwrap();
+        } else if let Some(d) = i.position(|c| c == '-') {
+            let label = &i[..d];
+            let h = hash(label);
+            boxes[h].retain(|(v, _)| !v.starts_with(label));
+        }
+    }
 
     Ok(boxes
         .iter()
@@ -57,7 +90,7 @@ fn run_2(input: &str) -> anyhow::Result<usize> {
         .map(|(box_idx, b)| {
             b.iter()
                 .enumerate()
-                .map(|(idx, f)| (box_idx + 1) * (idx + 1) * focal_len(f))
+                .map(|(idx, (_, f))| (box_idx + 1) * (idx + 1) * f)
                 .sum::<usize>()
         })
         .sum())
@@ -73,18 +106,18 @@ mod tests {
     }
 
     #[test]
-    fn day15_run_1() {
-        assert_eq!(
-            super::run_1("rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7").unwrap(),
-            1320
-        );
-    }
-
-    #[test]
-    fn day15_run_2() {
+    fn day15_validate() {
+        assert!(super::check_input(&crate::examples::input(15, "example")).is_empty());
         assert_eq!(
-            super::run_2("rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7").unwrap(),
-            145
+            super::check_input("rn=1,cm-,=3,qp=0,ab=12,Xy-,pc\not-"),
+            [
+                "Step 3: invalid step \"=3\"",
+                "Step 4: invalid step \"qp=0\"",
+                "Step 5: invalid step \"ab=12\"",
+                "Step 6: invalid step \"Xy-\"",
+                "Step 7: invalid step \"pc\"",
+                "The steps are on more than one line",
+            ]
         );
     }
 }
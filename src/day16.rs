@@ -1,18 +1,33 @@
 use rayon::prelude::*;
 use std::collections::HashSet;
 
-use crate::common::{Dir, Pos};
+use crate::common::{validate, Dir, Grid, Pos};
 
-pub fn run() -> anyhow::Result<()> {
-    let input = std::fs::read_to_string("day16.txt")?;
+pub struct Day;
 
-    println!("16:1 - {}", run_1(&input)?);
-    println!("16:2 - {}", run_2(&input)?);
+impl crate::solver::Solver for Day {
+    fn day(&self) -> usize {
+        16
+    }
+
+    fn title(&self) -> &'static str {
+        "The Floor Will Be Lava"
+    }
+
+    fn part1(&self, input: &str) -> anyhow::Result<crate::answer::Answer> {
+        Ok(run_1(input)?.into())
+    }
 
-    Ok(())
+    fn part2(&self, input: &str) -> anyhow::Result<crate::answer::Answer> {
+        Ok(run_2(input)?.into())
+    }
+
+    fn validate(&self, input: &str) -> Option<Vec<String>> {
+        Some(check_input(input))
+    }
 }
 
-fn calc_energized(map: &[Vec<char>], ray: Pos) -> usize {
+fn calc_energized(map: &Grid<char>, ray: Pos) -> usize {
     let mut rays = vec![ray];
     let mut visited = HashSet::new();
     let mut new_rays = Vec::new();
@@ -23,11 +38,11 @@ fn calc_energized(map: &[Vec<char>], ray: Pos) -> usize {
             if visited.contains(ray) {
                 continue;
             }
-            if ray.row() < map.len() && ray.col() < map[ray.row()].len() {
+            if let Some(tile) = map.get(ray.coord) {
                 visited.insert(*ray);
                 added = true;
 
-                match map[ray.row()][ray.col()] {
+                match tile {
                     '/' if (ray.dir == Dir::E || ray.dir == Dir::W) => {
                         ray.dir = ray.dir.turn_left();
                     }
@@ -53,11 +68,8 @@ fn calc_energized(map: &[Vec<char>], ray: Pos) -> usize {
                         new.dir = new.dir.turn_right();
                         new_rays.push(new);
                     }
-                    '-' => (),
-                    '.' => (),
-                    _ => {
-                        unreachable!();
-                    }
+                    // '-' and '.', anything else is rejected by parse_map
+                    _ => (),
                 }
 
                 let next = ray.move_forward();
@@ -72,8 +84,20 @@ fn calc_energized(map: &[Vec<char>], ray: Pos) -> usize {
     visited.len()
 }
 
+fn parse_map(input: &str) -> anyhow::Result<Grid<char>> {
+    Grid::parse(input, |c| match c {
+        '.' | '/' | '\\' | '|' | '-' => Ok(c),
+        _ => Err(anyhow::anyhow!("Invalid tile '{c}'")),
+    })
+}
+
+/// A grid of empty space, mirrors and splitters.
+fn check_input(input: &str) -> Vec<String> {
+    validate::grid(input, ".|-/\\")
+}
+
 fn run_1(input: &str) -> anyhow::Result<usize> {
-    let map: Vec<Vec<char>> = input.lines().map(|line| line.chars().collect()).collect();
+    let map = parse_map(input)?;
 
     Ok(calc_energized(
         &map,
@@ -85,10 +109,10 @@ fn run_1(input: &str) -> anyhow::Result<usize> {
 }
 
 fn run_2(input: &str) -> anyhow::Result<usize> {
-    let map: Vec<Vec<char>> = input.lines().map(|line| line.chars().collect()).collect();
+    let map = parse_map(input)?;
 
-    let rows = map.len();
-    let cols = map[0].len();
+    let rows = map.rows();
+    let cols = map.cols();
 
     let mut starting_points = Vec::new();
     for row in 0..rows {
@@ -115,29 +139,5 @@ fn run_2(input: &str) -> anyhow::Result<usize> {
         .par_iter()
         .map(|ray| calc_energized(&map, *ray))
         .max()
-        .unwrap())
-}
-
-#[cfg(test)]
-mod tests {
-    const INPUT: &str = r".|...\....
-|.-.\.....
-.....|-...
-........|.
-..........
-.........\
-..../.\\..
-.-.-/..|..
-.|....-|.\
-..//.|....";
-
-    #[test]
-    fn day16_run_1() {
-        assert_eq!(super::run_1(INPUT).unwrap(), 46);
-    }
-
-    #[test]
-    fn day16_run_2() {
-        assert_eq!(super::run_2(INPUT).unwrap(), 51);
-    }
+        .unwrap_or(0))
 }
@@ -1,12 +1,27 @@
-use crate::common::{Coord, Dir, Pos};
+use crate::common::{validate, Coord, Dir, Grid, Pos};
 
-pub fn run() -> anyhow::Result<()> {
-    let input = std::fs::read_to_string("day17.txt")?;
+pub struct Day;
 
-    println!("17:1 - {}", run_1(&input)?);
-    println!("17:2 - {}", run_2(&input)?);
+impl crate::solver::Solver for Day {
+    fn day(&self) -> usize {
+        17
+    }
+
+    fn title(&self) -> &'static str {
+        "Clumsy Crucible"
+    }
+
+    fn part1(&self, input: &str) -> anyhow::Result<crate::answer::Answer> {
+        Ok(run_1(input)?.into())
+    }
+
+    fn part2(&self, input: &str) -> anyhow::Result<crate::answer::Answer> {
+        Ok(run_2(input)?.into())
+    }
 
-    Ok(())
+    fn validate(&self, input: &str) -> Option<Vec<String>> {
+        Some(check_input(input))
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
@@ -40,19 +55,25 @@ impl Crucible {
     }
 }
 
-fn parse_map(input: &str) -> Vec<Vec<isize>> {
-    input
-        .lines()
-        .map(|line| {
-            line.chars()
-                .map(|c| c.to_digit(10).unwrap() as isize)
-                .collect()
-        })
-        .collect()
+/// A rectangle of heat loss digits.
+fn check_input(input: &str) -> Vec<String> {
+    validate::grid(input, "0123456789")
+}
+
+fn parse_map(input: &str) -> anyhow::Result<Grid<isize>> {
+    let map = Grid::parse(input, |c| {
+        c.to_digit(10)
+            .map(|d| d as isize)
+            .ok_or_else(|| anyhow::anyhow!("Invalid digit '{c}'"))
+    })?;
+    if map.rows() == 0 || map.cols() == 0 {
+        anyhow::bail!("Empty map");
+    }
+    Ok(map)
 }
 
-fn possible_moves(c: &Crucible, map: &[Vec<isize>]) -> Vec<(Crucible, isize)> {
-    let in_map = |c: &Crucible| map.get(c.pos.row()).and_then(|row| row.get(c.pos.col()));
+fn possible_moves(c: &Crucible, map: &Grid<isize>) -> Vec<(Crucible, isize)> {
+    let in_map = |c: &Crucible| map.get(c.pos.coord);
     let mut res = Vec::with_capacity(3);
 
     // Can only move forward three times in a row
@@ -77,7 +98,7 @@ fn possible_moves(c: &Crucible, map: &[Vec<isize>]) -> Vec<(Crucible, isize)> {
 }
 
 fn run_1(input: &str) -> anyhow::Result<usize> {
-    let map = parse_map(input);
+    let map = parse_map(input)?;
 
     let start = Crucible {
         pos: Pos {
@@ -87,11 +108,7 @@ fn run_1(input: &str) -> anyhow::Result<usize> {
         num_steps_forward: 0,
     };
 
-    let finish: Coord = (
-        (map.len() - 1) as isize,
-        (map[map.len() - 1].len() - 1) as isize,
-    )
-        .into();
+    let finish: Coord = (map.rows() - 1, map.cols() - 1).into();
 
     let (_path, cost) = pathfinding::directed::astar::astar(
         &start,
@@ -99,13 +116,13 @@ fn run_1(input: &str) -> anyhow::Result<usize> {
         |crucible| crucible.pos.coord.manhattan(&finish) as isize,
         |crucible| crucible.pos.coord == finish,
     )
-    .unwrap();
+    .ok_or_else(|| anyhow::anyhow!("No path to {finish:?}"))?;
 
     Ok(cost as usize)
 }
 
-fn possible_moves_uc(c: &Crucible, map: &[Vec<isize>]) -> Vec<(Crucible, isize)> {
-    let in_map = |c: &Crucible| map.get(c.pos.row()).and_then(|row| row.get(c.pos.col()));
+fn possible_moves_uc(c: &Crucible, map: &Grid<isize>) -> Vec<(Crucible, isize)> {
+    let in_map = |c: &Crucible| map.get(c.pos.coord);
     let mut res = Vec::with_capacity(3);
 
     // Can only move forward max 10 times in a row
@@ -133,7 +150,7 @@ fn possible_moves_uc(c: &Crucible, map: &[Vec<isize>]) -> Vec<(Crucible, isize)>
 }
 
 fn run_2(input: &str) -> anyhow::Result<usize> {
-    let map = parse_map(input);
+    let map = parse_map(input)?;
 
     let start = Crucible {
         pos: Pos {
@@ -143,11 +160,7 @@ fn run_2(input: &str) -> anyhow::Result<usize> {
         num_steps_forward: 0,
     };
 
-    let finish: Coord = (
-        (map.len() - 1) as isize,
-        (map[map.len() - 1].len() - 1) as isize,
-    )
-        .into();
+    let finish: Coord = (map.rows() - 1, map.cols() - 1).into();
 
     let (_path, cost) = pathfinding::directed::astar::astar(
         &start,
@@ -155,41 +168,16 @@ fn run_2(input: &str) -> anyhow::Result<usize> {
         |crucible| crucible.pos.coord.manhattan(&finish) as isize,
         |crucible| crucible.pos.coord == finish,
     )
-    .unwrap();
+    .ok_or_else(|| anyhow::anyhow!("No path to {finish:?}"))?;
 
     Ok(cost as usize)
 }
 
 #[cfg(test)]
 mod tests {
-    const INPUT: &str = "2413432311323
-3215453535623
-3255245654254
-3446585845452
-4546657867536
-1438598798454
-4457876987766
-3637877979653
-4654967986887
-4564679986453
-1224686865563
-2546548887735
-4322674655533";
-
-    #[test]
-    //#[ignore]
-    fn day17_run_1() {
-        assert_eq!(super::run_1(INPUT).unwrap(), 102);
-    }
-
-    #[test]
-    fn day17_run_2() {
-        assert_eq!(super::run_2(INPUT).unwrap(), 94);
-    }
-
     #[test]
     fn day17_possible_moves() {
-        let map = super::parse_map(INPUT);
+        let map = super::parse_map(&crate::examples::input(17, "example")).unwrap();
         let cruzible = super::Crucible {
             num_steps_forward: 3,
             pos: crate::common::Pos {
@@ -201,4 +189,23 @@ mod tests {
         dbg! {&possible_moves};
         assert_eq!(possible_moves.len(), 1);
     }
+
+    #[test]
+    fn day17_parse_error() {
+        let e = super::parse_map("123\n4x6").unwrap_err().to_string();
+        assert_eq!(e, "Invalid digit 'x' at row 2, col 2");
+        assert!(super::parse_map("").is_err());
+    }
+
+    #[test]
+    fn day17_validate() {
+        assert!(super::check_input(&crate::examples::input(17, "example")).is_empty());
+        assert_eq!(
+            super::check_input("123\n4x"),
+            [
+                "Line 2 has 2 cells, expected 3",
+                "Unexpected 'x' at line 2, column 2"
+            ]
+        );
+    }
 }
@@ -1,17 +1,30 @@
-use std::collections::HashSet;
-
 use crate::{
-    common::{Coord, Dir},
+    common::{geometry::Polygon, parse, validate, Dir},
     Input, PResult,
 };
 
-pub fn run() -> anyhow::Result<()> {
-    let input = std::fs::read_to_string("day18.txt")?;
+pub struct Day;
+
+impl crate::solver::Solver for Day {
+    fn day(&self) -> usize {
+        18
+    }
+
+    fn title(&self) -> &'static str {
+        "Lavaduct Lagoon"
+    }
+
+    fn part1(&self, input: &str) -> anyhow::Result<crate::answer::Answer> {
+        Ok(run_1(input)?.into())
+    }
 
-    println!("18:1 - {}", run_1(&input)?);
-    println!("18:2 - {}", run_2(&input)?);
+    fn part2(&self, input: &str) -> anyhow::Result<crate::answer::Answer> {
+        Ok(run_2(input)?.into())
+    }
 
-    Ok(())
+    fn validate(&self, input: &str) -> Option<Vec<String>> {
+        Some(check_input(input))
+    }
 }
 
 #[derive(Debug)]
@@ -21,95 +34,72 @@ struct Dig {
     color: usize,
 }
 
-// 42921 -- too high
-fn run_1(input: &str) -> anyhow::Result<usize> {
-    let (_, digs) = parse(input).map_err(|e| anyhow::anyhow!("{e}"))?;
-
-    let mut sides: HashSet<crate::common::Coord> = Default::default();
-    let mut last = (0, 0).into();
-    for dig in digs {
-        let mut cur = last;
-        sides.insert(cur);
-        for _ in 0..dig.steps {
-            cur = cur + dig.dir.movement();
-            sides.insert(cur);
+/// Each line is a dig, and both the plan and the one hidden in the colors
+/// end where they start, so that they enclose a lagoon.
+fn check_input(input: &str) -> Vec<String> {
+    let mut ends = [(0, 0); 2];
+    let mut problems = validate::lines(input, |line| {
+        let dig = match validate::parses(line, parse_dig) {
+            Ok(dig) => dig,
+            Err(e) => return vec![e],
+        };
+        let d = (dig.color & 0xf) as u32;
+        let Some(hidden) = Dir::from_digit(d) else {
+            return vec![format!(
+                "invalid direction digit {d} in color {:06x}",
+                dig.color
+            )];
+        };
+        let walk = [(dig.dir, dig.steps), (hidden, (dig.color >> 4) as isize)];
+        for ((row, col), (dir, steps)) in ends.iter_mut().zip(walk) {
+            *row += dir.movement().irow() * steps;
+            *col += dir.movement().icol() * steps;
+        }
+        vec![]
+    });
+    if problems.is_empty() {
+        for (plan, end) in ["dig plan", "plan in the colors"].into_iter().zip(ends) {
+            if end != (0, 0) {
+                problems.push(format!("The {plan} doesn't end where it starts"));
+            }
         }
-        last = cur;
     }
-
-    let seed = (1, 1).into();
-    assert!(!sides.contains(&seed));
-    let interiors = find_interiors(seed, &sides);
-    sides.extend(interiors);
-
-    Ok(sides.len())
+    problems
 }
 
-fn find_interiors(seed: Coord, sides: &HashSet<Coord>) -> std::collections::HashSet<Coord> {
-    let mut to_search = HashSet::new();
-    to_search.insert(seed);
-    let mut res: std::collections::HashSet<Coord> = Default::default();
-    while let Some(c) = to_search.iter().cloned().next() {
-        to_search.remove(&c);
-
-        if sides.contains(&c) {
-            continue;
-        }
-        res.insert(c);
-        let nbrs: Vec<_> = [Dir::N, Dir::E, Dir::S, Dir::W]
-            .into_iter()
-            .map(|d| c + d.movement())
-            .filter(|n| !res.contains(n))
-            .collect();
-
-        to_search.extend(nbrs);
-    }
+fn run_1(input: &str) -> anyhow::Result<usize> {
+    let digs = parse::parse_complete(input, parse)?;
 
-    res
+    Ok(lagoon_size(digs.iter().map(|dig| (dig.dir, dig.steps))))
 }
 
 fn run_2(input: &str) -> anyhow::Result<usize> {
-    let (_, digs) = parse(input).map_err(|e| anyhow::anyhow!("{e}"))?;
-
-    let mut sides: HashSet<crate::common::Coord> = Default::default();
-    let mut last = (0, 0).into();
-    for dig in digs {
-        let cur = last;
-        sides.insert(cur);
-
-        let dir = match dig.color & 0xf {
-            0 => Dir::E,
-            1 => Dir::S,
-            2 => Dir::W,
-            3 => Dir::N,
-            _ => unreachable!(),
-        };
-        let steps = dig.color >> 4;
-        println!("steps: {}, {:?}", steps, dir);
-
-        //panic!();
-        //for _ in 0..steps {
-        //    cur = cur + dir.movement();
-        //    sides.insert(cur);
-        //}
-        last = cur;
-    }
-
-    let seed = (1, 1).into();
-    assert!(!sides.contains(&seed));
-    //let interiors = find_interiors(seed, &sides);
-    //sides.extend(interiors);
+    let digs = parse::parse_complete(input, parse)?;
+
+    // The real instructions are hidden in the colors
+    let walk = digs
+        .iter()
+        .map(|dig| {
+            let d = dig.color & 0xf;
+            let dir = Dir::from_digit(d as u32).ok_or_else(|| {
+                anyhow::anyhow!("Invalid direction digit {d} in color {:06x}", dig.color)
+            })?;
+            Ok((dir, (dig.color >> 4) as isize))
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    Ok(lagoon_size(walk))
+}
 
-    Ok(sides.len())
+/// The cubic metres dug out by following `walk`.
+fn lagoon_size(walk: impl IntoIterator<Item = (Dir, isize)>) -> usize {
+    // The trench itself is dug out too
+    let lagoon = Polygon::from_walk((0, 0).into(), walk);
+    (lagoon.interior_points() + lagoon.boundary_points()) as usize
 }
 
 fn parse_dir(i: Input) -> PResult<Dir> {
-    let down = nom::combinator::map(nom::bytes::complete::tag("D"), |_| Dir::S);
-    let up = nom::combinator::map(nom::bytes::complete::tag("U"), |_| Dir::N);
-    let left = nom::combinator::map(nom::bytes::complete::tag("L"), |_| Dir::W);
-    let right = nom::combinator::map(nom::bytes::complete::tag("R"), |_| Dir::E);
-    let res = nom::branch::alt((down, up, left, right))(i)?;
-    Ok(res)
+    nom::combinator::map_opt(nom::character::complete::anychar, Dir::from_udlr)(i)
 }
 
 fn parse_color(i: Input) -> PResult<usize> {
@@ -132,32 +122,23 @@ fn parse_dig(i: Input) -> PResult<Dig> {
 }
 
 fn parse(i: Input) -> PResult<Vec<Dig>> {
-    nom::multi::separated_list1(nom::character::complete::newline, parse_dig)(i)
+    nom::multi::separated_list1(
+        nom::character::complete::newline,
+        nom::error::context("dig plan", parse_dig),
+    )(i)
 }
 
 #[cfg(test)]
 mod tests {
-    const INPUT: &str = "R 6 (#70c710)
-D 5 (#0dc571)
-L 2 (#5713f0)
-D 2 (#d2c081)
-R 2 (#59c680)
-D 2 (#411b91)
-L 5 (#8ceee2)
-U 2 (#caa173)
-L 1 (#1b58a2)
-U 2 (#caa171)
-R 2 (#7807d2)
-U 3 (#a77fa3)
-L 2 (#015232)
-U 2 (#7a21e3)";
-
     #[test]
     fn day18_dir() {
         assert_eq!(super::parse_dir("D").unwrap().1, crate::common::Dir::S);
         assert_eq!(super::parse_dir("U").unwrap().1, crate::common::Dir::N);
         assert_eq!(super::parse_dir("L").unwrap().1, crate::common::Dir::W);
         assert_eq!(super::parse_dir("R").unwrap().1, crate::common::Dir::E);
+        for c in ["N", "^", "2"] {
+            assert!(super::parse_dir(c).is_err());
+        }
     }
 
     #[test]
@@ -167,36 +148,30 @@ U 2 (#7a21e3)";
     }
     #[test]
     fn day18_parse() {
-        assert_eq!(super::parse(INPUT).unwrap().1.len(), 14);
+        let input = crate::examples::input(18, "example");
+        assert_eq!(super::parse(&input).unwrap().1.len(), 14);
     }
 
     #[test]
-    fn day18_run_1() {
-        assert_eq!(super::run_1(INPUT).unwrap(), 62);
+    fn day18_validate() {
+        assert!(super::check_input(&crate::examples::input(18, "example")).is_empty());
+        assert_eq!(
+            super::check_input("R 2 (#000020)\nR x (#000000)\nL 2 (#000004)"),
+            [
+                "Line 2: column 3: expected Digit, found 'x'",
+                "Line 3: invalid direction digit 4 in color 000004",
+            ]
+        );
+        assert_eq!(
+            super::check_input("R 2 (#000020)\nL 1 (#000022)"),
+            ["The dig plan doesn't end where it starts"]
+        );
     }
 
     #[test]
-    fn day18_interiors() {
-        let mut sides: std::collections::HashSet<crate::common::Coord> = Default::default();
-        sides.insert((0, 0).into());
-        sides.insert((0, 1).into());
-        sides.insert((0, 2).into());
-        sides.insert((0, 3).into());
-        sides.insert((1, 0).into());
-        sides.insert((1, 3).into());
-        sides.insert((2, 0).into());
-        sides.insert((2, 3).into());
-        sides.insert((3, 0).into());
-        sides.insert((3, 1).into());
-        sides.insert((3, 2).into());
-        sides.insert((3, 3).into());
-        let ints = super::find_interiors((1, 1).into(), &sides);
-        assert_eq!(ints.len(), 4);
-    }
-
-    #[test]
-    #[ignore]
-    fn day18_run_2() {
-        assert_eq!(super::run_2(INPUT).unwrap(), 952408144115);
+    fn day18_run_1() {
+        // (1, 1) is outside this one
+        let up_left = "L 2 (#000000)\nU 2 (#000000)\nR 2 (#000000)\nD 2 (#000000)";
+        assert_eq!(super::run_1(up_left).unwrap(), 9);
     }
 }
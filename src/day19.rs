@@ -3,16 +3,37 @@ use std::collections::HashMap;
 use nom::{
     bytes::complete::tag,
     character::complete::{alpha1, newline},
+    error::context,
     multi::separated_list1,
 };
 
-pub fn run() -> anyhow::Result<()> {
-    let input = std::fs::read_to_string("day19.txt")?;
+use crate::common::{
+    intervals::{Cuboid, Interval},
+    parse, validate,
+};
+
+pub struct Day;
 
-    println!("19:1 - {}", run_1(&input)?);
-    println!("19:2 - {}", run_2(&input)?);
+impl crate::solver::Solver for Day {
+    fn day(&self) -> usize {
+        19
+    }
+
+    fn title(&self) -> &'static str {
+        "Aplenty"
+    }
+
+    fn part1(&self, input: &str) -> anyhow::Result<crate::answer::Answer> {
+        Ok(run_1(input)?.into())
+    }
+
+    fn part2(&self, input: &str) -> anyhow::Result<crate::answer::Answer> {
+        Ok(run_2(input)?.into())
+    }
 
-    Ok(())
+    fn validate(&self, input: &str) -> Option<Vec<String>> {
+        Some(check_input(input))
+    }
 }
 
 #[derive(Debug)]
@@ -27,9 +48,14 @@ impl MachinePart {
     fn sum(&self) -> usize {
         self.x + self.m + self.a + self.s
     }
+
+    /// The rating by its index in "xmas".
+    fn rating(&self, category: usize) -> usize {
+        [self.x, self.m, self.a, self.s][category]
+    }
 }
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 enum RuleRes {
     Accepted,
     Rejected,
@@ -43,43 +69,75 @@ struct System {
     parts: Vec<MachinePart>,
 }
 
-type Rule = Box<dyn Fn(&MachinePart) -> RuleRes + Sync>;
+/// A comparison of one rating, e.g. `a<2006`.
+struct Condition {
+    category: usize,
+    less_than: bool,
+    value: usize,
+}
+
+/// Where a part goes if it matches the condition, or always without one.
+struct Rule {
+    condition: Option<Condition>,
+    res: RuleRes,
+}
+
+impl Rule {
+    fn apply(&self, part: &MachinePart) -> RuleRes {
+        let matches = self.condition.as_ref().is_none_or(|c| {
+            let rating = part.rating(c.category);
+            if c.less_than {
+                rating < c.value
+            } else {
+                rating > c.value
+            }
+        });
+        if matches {
+            self.res.clone()
+        } else {
+            RuleRes::NoMatch
+        }
+    }
+}
+
+fn parse_res(i: crate::Input) -> crate::PResult<RuleRes> {
+    nom::combinator::map(alpha1, |v| match v {
+        "A" => RuleRes::Accepted,
+        "R" => RuleRes::Rejected,
+        v => RuleRes::Next(v.to_string()),
+    })(i)
+}
 
 fn parse_complex_rule(i: crate::Input) -> crate::PResult<Rule> {
     let lt = nom::combinator::map(tag("<"), |_| true);
     let gt = nom::combinator::map(tag(">"), |_| false);
 
-    let (i, var) = nom::combinator::map(alpha1, |s: &str| s.to_string())(i)?;
-    let (i, lt) = nom::branch::alt((lt, gt))(i)?;
-    let (i, num) = nom::combinator::map(nom::character::complete::u64, |v| v as usize)(i)?;
+    let (i, category) = nom::combinator::map(nom::character::complete::one_of("xmas"), |c| {
+        "xmas".find(c).unwrap_or_default()
+    })(i)?;
+    let (i, less_than) = nom::branch::alt((lt, gt))(i)?;
+    let (i, value) = nom::combinator::map(nom::character::complete::u64, |v| v as usize)(i)?;
 
     let (i, _) = tag(":")(i)?;
-    let (i, next) = nom::combinator::map(alpha1, |s: &str| s.to_string())(i)?;
-
-    let f = Box::new(move |m: &MachinePart| match (var.as_str(), lt) {
-        ("x", true) if m.x < num => RuleRes::Next(next.clone()),
-        ("x", false) if m.x > num => RuleRes::Next(next.clone()),
-        ("m", true) if m.m < num => RuleRes::Next(next.clone()),
-        ("m", false) if m.m > num => RuleRes::Next(next.clone()),
-        ("a", true) if m.a < num => RuleRes::Next(next.clone()),
-        ("a", false) if m.a > num => RuleRes::Next(next.clone()),
-        ("s", true) if m.s < num => RuleRes::Next(next.clone()),
-        ("s", false) if m.s > num => RuleRes::Next(next.clone()),
-        _ => RuleRes::NoMatch,
-    });
+    let (i, res) = parse_res(i)?;
 
-    Ok((i, f))
+    let condition = Some(Condition {
+        category,
+        less_than,
+        value,
+    });
+    Ok((i, Rule { condition, res }))
 }
+
 fn parse_simple_rule(i: crate::Input) -> crate::PResult<Rule> {
-    let (i, v) = alpha1(i)?;
-    match v {
-        "A" => Ok((i, Box::new(|_: &MachinePart| RuleRes::Accepted))),
-        "R" => Ok((i, Box::new(|_: &MachinePart| RuleRes::Rejected))),
-        v => {
-            let v = v.to_string();
-            Ok((i, Box::new(move |_: &MachinePart| RuleRes::Next(v.clone()))))
-        }
-    }
+    let (i, res) = parse_res(i)?;
+    Ok((
+        i,
+        Rule {
+            condition: None,
+            res,
+        },
+    ))
 }
 
 fn parse_workflow(i: crate::Input) -> crate::PResult<(String, Vec<Rule>)> {
@@ -108,29 +166,85 @@ fn parse_machine_part(i: crate::Input) -> crate::PResult<MachinePart> {
 }
 
 fn parse(i: crate::Input) -> crate::PResult<System> {
-    let (i, wfs) = separated_list1(newline, parse_workflow)(i)?;
+    let (i, wfs) = separated_list1(newline, context("workflow", parse_workflow))(i)?;
 
-    let (i, _) = newline(i)?;
-    let (i, _) = newline(i)?;
+    let (i, _) = parse::blank_line(i)?;
 
-    let (i, parts) = separated_list1(newline, parse_machine_part)(i)?;
+    let (i, parts) = separated_list1(newline, context("part", parse_machine_part))(i)?;
 
     let workflows = wfs.into_iter().collect();
 
     Ok((i, System { workflows, parts }))
 }
 
+/// Workflows, a blank line and parts. Each workflow is defined once and
+/// ends in a rule without a condition, and the workflows a part goes
+/// through are defined, starting with `in`.
+fn check_input(input: &str) -> Vec<String> {
+    let mut problems = vec![];
+    let mut workflows = HashMap::new();
+    let mut lines = input.lines().enumerate();
+    for (row, line) in lines.by_ref().take_while(|(_, line)| !line.is_empty()) {
+        match validate::parses(line, parse_workflow) {
+            Ok((name, rules)) => {
+                if rules.last().is_some_and(|r| r.condition.is_some()) {
+                    problems.push(format!(
+                        "Line {}: the last rule of {name} has a condition",
+                        row + 1
+                    ));
+                }
+                if workflows.insert(name.clone(), rules).is_some() {
+                    problems.push(format!(
+                        "Line {}: workflow {name} is defined again",
+                        row + 1
+                    ));
+                }
+            }
+            Err(e) => problems.push(format!("Line {}: {e}", row + 1)),
+        }
+    }
+    let mut parts = 0;
+    for (row, line) in lines {
+        match validate::parses(line, parse_machine_part) {
+            Ok(_) => parts += 1,
+            Err(e) => problems.push(format!("Line {}: {e}", row + 1)),
+        }
+    }
+    if parts == 0 {
+        problems.push("There are no parts".to_string());
+    }
+
+    let mut unknown: Vec<&str> = workflows
+        .values()
+        .flatten()
+        .filter_map(|rule| match &rule.res {
+            RuleRes::Next(n) if !workflows.contains_key(n) => Some(n.as_str()),
+            _ => None,
+        })
+        .chain((!workflows.contains_key("in")).then_some("in"))
+        .collect();
+    unknown.sort();
+    unknown.dedup();
+    for n in unknown {
+        problems.push(format!("Workflow {n} is used but not defined"));
+    }
+    problems
+}
+
 fn run_1(input: &str) -> anyhow::Result<usize> {
-    let (_, system) = parse(input).map_err(|e| anyhow::anyhow!("{e}"))?;
+    let system = parse::parse_complete(input, parse)?;
 
     let mut accepted = Vec::new();
 
     for part in system.parts {
         let mut cur = "in".to_string();
         'part_loop: loop {
-            let rules = system.workflows.get(&cur).unwrap();
+            let rules = system
+                .workflows
+                .get(&cur)
+                .ok_or_else(|| anyhow::anyhow!("Unknown workflow '{cur}'"))?;
             for rule in rules.iter() {
-                match rule(&part) {
+                match rule.apply(&part) {
                     RuleRes::Accepted => {
                         accepted.push(part);
                         break 'part_loop;
@@ -160,148 +274,51 @@ fn run_1(input: &str) -> anyhow::Result<usize> {
     Ok(accepted.iter().map(|m| m.sum()).sum())
 }
 
-fn is_accepted(part: MachinePart, wfs: &HashMap<String, Vec<Rule>>) -> bool {
-    let mut cur = "in".to_string();
-    loop {
-        let rules = wfs.get(&cur).unwrap();
-        for rule in rules.iter() {
-            match rule(&part) {
-                RuleRes::Accepted => {
-                    return true;
-                }
-                RuleRes::Rejected => {
-                    return false;
-                }
-                RuleRes::Next(n) if n == "A" => {
-                    return true;
-                }
-                RuleRes::Next(n) if n == "R" => {
-                    return false;
-                }
-                RuleRes::Next(n) => {
-                    cur = n.clone();
-                    break;
-                }
-                RuleRes::NoMatch => {
-                    continue;
-                }
+/// The number of parts in `parts` that workflow `name` accepts.
+fn count_accepted(
+    name: &str,
+    mut parts: Cuboid<4>,
+    wfs: &HashMap<String, Vec<Rule>>,
+) -> anyhow::Result<usize> {
+    let rules = wfs
+        .get(name)
+        .ok_or_else(|| anyhow::anyhow!("Unknown workflow '{name}'"))?;
+    let mut count = 0;
+    for rule in rules {
+        let (matching, rest) = match &rule.condition {
+            None => (Some(parts), None),
+            Some(c) if c.less_than => parts.split(c.category, c.value as i64),
+            Some(c) => {
+                let (below, above) = parts.split(c.category, c.value as i64 + 1);
+                (above, below)
             }
+        };
+        if let Some(matching) = matching {
+            count += match &rule.res {
+                RuleRes::Accepted => matching.volume(),
+                RuleRes::Next(n) => count_accepted(n, matching, wfs)?,
+                RuleRes::Rejected | RuleRes::NoMatch => 0,
+            };
+        }
+        match rest {
+            Some(rest) => parts = rest,
+            None => break,
         }
     }
+    Ok(count)
 }
 
 fn run_2(input: &str) -> anyhow::Result<usize> {
-    let (_, system) = parse(input).map_err(|e| anyhow::anyhow!("{e}"))?;
-
-    let (xmin, xmax) = (1..4001).fold((usize::MAX, usize::MIN), |(min, max), x| {
-        if is_accepted(
-            MachinePart {
-                x,
-                m: 0,
-                a: 0,
-                s: 0,
-            },
-            &system.workflows,
-        ) {
-            (min.min(x), max.max(x))
-        } else {
-            (min, max)
-        }
-    });
-    println!("x: {xmin} {xmax}");
-
-    let (mmin, mmax) = (1..4001).fold((usize::MAX, usize::MIN), |(min, max), m| {
-        if is_accepted(
-            MachinePart {
-                x: 0,
-                m,
-                a: 0,
-                s: 0,
-            },
-            &system.workflows,
-        ) {
-            (min.min(m), max.max(m))
-        } else {
-            (min, max)
-        }
-    });
-    println!("m: {mmin} {mmax}");
-
-    let (amin, amax) = (1..4001).fold((usize::MAX, usize::MIN), |(min, max), a| {
-        if is_accepted(
-            MachinePart {
-                x: 0,
-                m: 0,
-                a,
-                s: 0,
-            },
-            &system.workflows,
-        ) {
-            (min.min(a), max.max(a))
-        } else {
-            (min, max)
-        }
-    });
-    println!("a: {amin} {amax}");
-
-    let (smin, smax) = (1..4001).fold((usize::MAX, usize::MIN), |(min, max), s| {
-        if is_accepted(
-            MachinePart {
-                x: 0,
-                m: 0,
-                a: 0,
-                s,
-            },
-            &system.workflows,
-        ) {
-            (min.min(s), max.max(s))
-        } else {
-            (min, max)
-        }
-    });
-    println!("s: {smin} {smax}");
-
-    // let tot = (xmin..=xmax).into_par_iter().map(|x| {
-    //     (mmin..=mmax).map(|m| {
-    //         (amin..=amax).map(|a| {
-    //             (smin..=smax).map(|s| {
-    //                 if is_accepted(MachinePart{x,m,a,s}, &system.workflows) {
-    //                     1
-    //                 }
-    //                 else {
-    //                     0
-    //                 }
-    //             }).sum::<usize>()
-    //         }).sum::<usize>()
-    //     }).sum::<usize>()
-    // }).sum();
-
-    Ok((xmax - xmin + 1) * (mmax - mmin + 1) * (amax - amin + 1) * (smax - smin + 1))
-    // Ok(tot)
+    let system = parse::parse_complete(input, parse)?;
+
+    let ratings = Interval::new(1, 4001);
+    count_accepted("in", Cuboid::new([ratings; 4]), &system.workflows)
 }
 
 #[cfg(test)]
 mod tests {
     use crate::day19::RuleRes;
 
-    const INPUT: &str = "px{a<2006:qkq,m>2090:A,rfg}
-pv{a>1716:R,A}
-lnx{m>1548:A,A}
-rfg{s<537:gd,x>2440:R,A}
-qs{s>3448:A,lnx}
-qkq{x<1416:A,crn}
-crn{x>2662:A,R}
-in{s<1351:px,qqz}
-qqz{s>2770:qs,m<1801:hdj,R}
-gd{a>3333:R,R}
-hdj{m>838:A,pv}
-
-{x=787,m=2655,a=1222,s=2876}
-{x=1679,m=44,a=2067,s=496}
-{x=2036,m=264,a=79,s=2244}
-{x=2461,m=1339,a=466,s=291}
-{x=2127,m=1623,a=2188,s=1013}";
-
     #[test]
     fn day19_parse() {
         let (i, f) = super::parse_complex_rule("a<2006:qkq").unwrap();
@@ -312,24 +329,34 @@ hdj{m>838:A,pv}
             a: 30,
             s: 40,
         };
-        assert_eq!(f(&v), RuleRes::Next("qkq".to_string()));
+        assert_eq!(f.apply(&v), RuleRes::Next("qkq".to_string()));
         let v = super::MachinePart {
             x: 10,
             m: 20,
             a: 3000,
             s: 40,
         };
-        assert_eq!(f(&v), RuleRes::NoMatch);
-    }
-
-    #[test]
-    fn day19_run_1() {
-        assert_eq!(super::run_1(INPUT).unwrap(), 19114);
+        assert_eq!(f.apply(&v), RuleRes::NoMatch);
     }
 
     #[test]
-    #[ignore]
-    fn day19_run_2() {
-        assert_eq!(super::run_2(INPUT).unwrap(), 167409079868000);
+    fn day19_validate() {
+        assert!(super::check_input(&crate::examples::input(19, "example")).is_empty());
+        assert_eq!(
+            super::check_input("in{a<5:px,R}\npx{a>1:A}\npx{A}\n\n{x=1,m=2,a=3}"),
+            [
+                "Line 2: the last rule of px has a condition",
+                "Line 3: workflow px is defined again",
+                "Line 5: column 13: expected Tag, found '}'",
+                "There are no parts",
+            ]
+        );
+        assert_eq!(
+            super::check_input("qs{s>5:A,lnx}\n\n{x=1,m=2,a=3,s=4}"),
+            [
+                "Workflow in is used but not defined",
+                "Workflow lnx is used but not defined",
+            ]
+        );
     }
 }
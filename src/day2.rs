@@ -1,10 +1,27 @@
-pub fn run() -> anyhow::Result<()> {
-    let input = std::fs::read_to_string("day2.txt")?;
+use crate::common::{parse, validate};
 
-    println!("2:1 - {}", run_1(&input)?);
-    println!("2:2 - {}", run_2(&input)?);
+pub struct Day;
 
-    Ok(())
+impl crate::solver::Solver for Day {
+    fn day(&self) -> usize {
+        2
+    }
+
+    fn title(&self) -> &'static str {
+        "Cube Conundrum"
+    }
+
+    fn part1(&self, input: &str) -> anyhow::Result<crate::answer::Answer> {
+        Ok(run_1(input)?.into())
+    }
+
+    fn part2(&self, input: &str) -> anyhow::Result<crate::answer::Answer> {
+        Ok(run_2(input)?.into())
+    }
+
+    fn validate(&self, input: &str) -> Option<Vec<String>> {
+        Some(check_input(input))
+    }
 }
 
 enum Cube {
@@ -66,11 +83,24 @@ fn parse_game(i: crate::Input) -> crate::PResult<Game> {
 }
 
 fn parse(i: crate::Input) -> crate::PResult<Vec<Game>> {
-    nom::multi::separated_list1(nom::character::complete::newline, parse_game)(i)
+    nom::multi::separated_list1(
+        nom::character::complete::newline,
+        nom::error::context("game", parse_game),
+    )(i)
+}
+
+/// Each line is a game in the format `parse_game` reads.
+fn check_input(input: &str) -> Vec<String> {
+    validate::lines(input, |line| {
+        validate::parses(line, parse_game)
+            .err()
+            .into_iter()
+            .collect()
+    })
 }
 
 fn run_1(input: &str) -> anyhow::Result<usize> {
-    let (_, games) = parse(input).map_err(|e| anyhow::anyhow!(e.to_string()))?;
+    let games = parse::parse_complete(input, parse)?;
     let check = GameSet {
         red: 12,
         green: 13,
@@ -89,7 +119,7 @@ fn run_1(input: &str) -> anyhow::Result<usize> {
 }
 
 fn run_2(input: &str) -> anyhow::Result<usize> {
-    let (_, games) = parse(input).map_err(|e| anyhow::anyhow!(e.to_string()))?;
+    let games = parse::parse_complete(input, parse)?;
     Ok(games
         .iter()
         .map(|game| {
@@ -109,22 +139,3 @@ fn run_2(input: &str) -> anyhow::Result<usize> {
         .map(|set| set.red * set.green * set.blue)
         .sum())
 }
-
-#[cfg(test)]
-mod tests {
-    const INPUT: &str = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
-Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
-Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
-Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
-Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
-
-    #[test]
-    fn day2_run_1() {
-        assert_eq!(super::run_1(INPUT).unwrap(), 8);
-    }
-
-    #[test]
-    fn day2_run_2() {
-        assert_eq!(super::run_2(INPUT).unwrap(), 2286)
-    }
-}
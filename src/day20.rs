@@ -1,18 +1,35 @@
 use std::collections::{HashMap, VecDeque};
 
-use crate::Input;
+use crate::{
+    common::{parse, validate},
+    Input,
+};
 use nom::{
-    bytes::complete::tag, character::complete::newline, multi::separated_list1,
-    sequence::separated_pair,
+    bytes::complete::tag, character::complete::newline, error::context, multi::separated_list1,
 };
 
-pub fn run() -> anyhow::Result<()> {
-    let input = std::fs::read_to_string("day20.txt")?;
+pub struct Day;
 
-    println!("20:1 - {}", run_1(&input)?);
-    println!("20:2 - {}", run_2(&input)?);
+impl crate::solver::Solver for Day {
+    fn day(&self) -> usize {
+        20
+    }
 
-    Ok(())
+    fn title(&self) -> &'static str {
+        "Pulse Propagation"
+    }
+
+    fn part1(&self, input: &str) -> anyhow::Result<crate::answer::Answer> {
+        Ok(run_1(input)?.into())
+    }
+
+    fn part2(&self, input: &str) -> anyhow::Result<crate::answer::Answer> {
+        Ok(run_2(input)?.into())
+    }
+
+    fn validate(&self, input: &str) -> Option<Vec<String>> {
+        Some(check_input(input))
+    }
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -28,14 +45,11 @@ enum ModType {
     Conjunction,
 }
 
+type Signals = VecDeque<(String, String, Pulse)>;
+
 trait Module: std::fmt::Debug {
-    fn add_input(&mut self, i: &str);
-    fn set_input(
-        &mut self,
-        from: &str,
-        to: &str,
-        pulse: Pulse,
-    ) -> VecDeque<(String, String, Pulse)>;
+    fn add_input(&mut self, i: &str) -> anyhow::Result<()>;
+    fn set_input(&mut self, from: &str, to: &str, pulse: Pulse) -> anyhow::Result<Signals>;
     fn get_outputs(&self) -> Vec<String>;
 }
 
@@ -45,20 +59,16 @@ struct Broadcaster {
 }
 
 impl Module for Broadcaster {
-    fn add_input(&mut self, _o: &str) {
-        unreachable!()
+    fn add_input(&mut self, i: &str) -> anyhow::Result<()> {
+        anyhow::bail!("Module {i} sends pulses to the broadcaster")
     }
 
-    fn set_input(
-        &mut self,
-        _from: &str,
-        to: &str,
-        pulse: Pulse,
-    ) -> VecDeque<(String, String, Pulse)> {
-        self.outputs
+    fn set_input(&mut self, _from: &str, to: &str, pulse: Pulse) -> anyhow::Result<Signals> {
+        Ok(self
+            .outputs
             .iter()
             .map(|o| (to.to_string(), o.to_string(), pulse))
-            .collect()
+            .collect())
     }
 
     fn get_outputs(&self) -> Vec<String> {
@@ -81,24 +91,22 @@ struct FlipFlop {
 }
 
 impl Module for FlipFlop {
-    fn add_input(&mut self, _i: &str) {}
-
-    fn set_input(
-        &mut self,
-        _from: &str,
-        to: &str,
-        pulse: Pulse,
-    ) -> VecDeque<(String, String, Pulse)> {
+    fn add_input(&mut self, _i: &str) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    fn set_input(&mut self, _from: &str, to: &str, pulse: Pulse) -> anyhow::Result<Signals> {
         if Pulse::Low == pulse {
             self.on = !self.on;
             let new_pulse = if self.on { Pulse::High } else { Pulse::Low };
 
-            self.outputs
+            Ok(self
+                .outputs
                 .iter()
                 .map(|o| (to.to_string(), o.to_string(), new_pulse))
-                .collect()
+                .collect())
         } else {
-            VecDeque::new()
+            Ok(VecDeque::new())
         }
     }
     fn get_outputs(&self) -> Vec<String> {
@@ -122,23 +130,23 @@ struct Conjunction {
 }
 
 impl Module for Conjunction {
-    fn add_input(&mut self, o: &str) {
+    fn add_input(&mut self, o: &str) -> anyhow::Result<()> {
         self.inputs.insert(o.to_string(), Pulse::Low);
+        Ok(())
     }
 
-    fn set_input(
-        &mut self,
-        from: &str,
-        to: &str,
-        pulse: Pulse,
-    ) -> VecDeque<(String, String, Pulse)> {
-        *self.inputs.get_mut(from).unwrap() = pulse;
+    fn set_input(&mut self, from: &str, to: &str, pulse: Pulse) -> anyhow::Result<Signals> {
+        let Some(last) = self.inputs.get_mut(from) else {
+            anyhow::bail!("Module {to} got a pulse from {from}, which isn't one of its inputs");
+        };
+        *last = pulse;
         let all_high = self.inputs.values().all(|p| *p == Pulse::High);
         let new_pulse = if all_high { Pulse::Low } else { Pulse::High };
-        self.outputs
+        Ok(self
+            .outputs
             .iter()
             .map(|o| (to.to_string(), o.to_string(), new_pulse))
-            .collect()
+            .collect())
     }
     fn get_outputs(&self) -> Vec<String> {
         self.outputs.clone()
@@ -154,8 +162,37 @@ impl Conjunction {
     }
 }
 
+/// One module per line, each defined once. Only the broadcaster has no
+/// type, and nothing sends pulses to it.
+fn check_input(input: &str) -> Vec<String> {
+    let mut names = vec![];
+    let mut problems = validate::lines(input, |line| {
+        let (name, (t, outputs)) = match validate::parses(line, row_parser) {
+            Ok(row) => row,
+            Err(e) => return vec![e],
+        };
+        let mut problems = vec![];
+        if matches!(t, ModType::Broadcaster) != (name == "broadcaster") {
+            problems.push(format!("module {name} has the wrong type"));
+        }
+        if outputs.iter().any(|o| o == "broadcaster") {
+            problems.push(format!("module {name} sends pulses to the broadcaster"));
+        }
+        if names.contains(&name) {
+            problems.push(format!("module {name} is defined again"));
+        }
+        names.push(name);
+        problems
+    });
+    if !input.trim().is_empty() && !names.iter().any(|n| n == "broadcaster") {
+        problems.push("There is no broadcaster".to_string());
+    }
+    problems
+}
+
 fn run_1(input: &str) -> anyhow::Result<usize> {
-    let (_i, mut map) = parse(input).map_err(|e| anyhow::anyhow!(e.to_string()))?;
+    let mut map = parse::parse_complete(input, parse)?;
+    connect(&mut map)?;
     let mut low_pulses = 0;
     let mut high_pulses = 0;
     for _ in 0..1000 {
@@ -163,10 +200,10 @@ fn run_1(input: &str) -> anyhow::Result<usize> {
         low_pulses += 1;
         let bcast = "broadcaster";
 
-        let mut signals_to_process =
-            map.get_mut(bcast)
-                .unwrap()
-                .set_input("button", bcast, Pulse::Low);
+        let mut signals_to_process = map
+            .get_mut(bcast)
+            .ok_or_else(|| anyhow::anyhow!("No {bcast} module"))?
+            .set_input("button", bcast, Pulse::Low)?;
 
         while let Some((from, to, pulse)) = signals_to_process.pop_front() {
             if pulse == Pulse::Low {
@@ -176,7 +213,7 @@ fn run_1(input: &str) -> anyhow::Result<usize> {
             };
             //println!("{from} -{pulse:?} -> {to}");
             if let Some(module) = map.get_mut(&to) {
-                let mut new_signals = module.set_input(&from, &to, pulse);
+                let mut new_signals = module.set_input(&from, &to, pulse)?;
                 //new_signals.append(&mut signals_to_process);
                 signals_to_process.append(&mut new_signals);
             }
@@ -195,7 +232,7 @@ fn run_2(_input: &str) -> anyhow::Result<usize> {
     //    let mut signals_to_process =
     //        map.get_mut(bcast)
     //            .unwrap()
-    //            .set_input("button", bcast, Pulse::Low);
+    //            .set_input("button", bcast, Pulse::Low)?;
     //
     //    while let Some((from, to, pulse)) = signals_to_process.pop_front() {
     //        if let Some(module) = map.get_mut(&to) {
@@ -210,15 +247,9 @@ fn run_2(_input: &str) -> anyhow::Result<usize> {
     //
     //    loop_cnt+=1;
     //}
-    todo!()
+    Err(crate::solver::NotImplemented.into())
 }
 
-fn output_parser(i: crate::Input) -> crate::PResult<Vec<String>> {
-    separated_list1(
-        tag(", "),
-        nom::combinator::map(nom::character::complete::alpha1, |s: &str| s.to_string()),
-    )(i)
-}
 fn type_parser(i: crate::Input) -> crate::PResult<ModType> {
     let flip_flop = nom::combinator::map(tag("%"), |_| ModType::FlipFlop);
     let conj = nom::combinator::map(tag("&"), |_| ModType::Conjunction);
@@ -233,14 +264,14 @@ fn type_parser(i: crate::Input) -> crate::PResult<ModType> {
 }
 fn row_parser(i: Input) -> crate::PResult<(String, (ModType, Vec<String>))> {
     let (i, t) = type_parser(i)?;
-    let (i, (name, outputs)) =
-        separated_pair(nom::character::complete::alpha1, tag(" -> "), output_parser)(i)?;
+    let (i, (name, outputs)) = parse::adjacency(i)?;
+    let outputs = outputs.into_iter().map(String::from).collect();
 
     Ok((i, (name.to_string(), (t, outputs))))
 }
 
 fn parse(i: crate::Input) -> crate::PResult<HashMap<String, Box<dyn Module>>> {
-    let (i, rows) = separated_list1(newline, row_parser)(i)?;
+    let (i, rows) = separated_list1(newline, context("module", row_parser))(i)?;
 
     let mut res: HashMap<String, Box<dyn Module>> = HashMap::new();
 
@@ -258,37 +289,46 @@ fn parse(i: crate::Input) -> crate::PResult<HashMap<String, Box<dyn Module>>> {
         }
     }
 
-    // Update the inputs
-    let names: Vec<_> = res.keys().cloned().collect();
+    Ok((i, res))
+}
+
+/// Tells every module which modules send pulses to it.
+fn connect(map: &mut HashMap<String, Box<dyn Module>>) -> anyhow::Result<()> {
+    let names: Vec<_> = map.keys().cloned().collect();
     for name in names {
-        let outputs = res[&name].get_outputs();
+        let outputs = map[&name].get_outputs();
         for output in outputs {
-            if let Some(output) = res.get_mut(&output) {
-                output.add_input(&name);
+            if let Some(output) = map.get_mut(&output) {
+                output.add_input(&name)?;
             }
         }
     }
-
-    Ok((i, res))
+    Ok(())
 }
 
 #[cfg(test)]
 mod tests {
-    const INPUT_1: &str = "broadcaster -> a, b, c
-%a -> b
-%b -> c
-%c -> inv
-&inv -> a";
-
-    const INPUT_2: &str = "broadcaster -> a
-%a -> inv, con
-&inv -> b
-%b -> con
-&con -> output";
+    #[test]
+    fn day20_bad_wiring() {
+        let e = super::run_1("broadcaster -> a\n%a -> broadcaster").unwrap_err();
+        assert_eq!(e.to_string(), "Module a sends pulses to the broadcaster");
+    }
 
     #[test]
-    fn day20_run_1() {
-        assert_eq!(super::run_1(INPUT_1).unwrap(), 32000000);
-        assert_eq!(super::run_1(INPUT_2).unwrap(), 11687500);
+    fn day20_validate() {
+        for name in ["simple", "interesting"] {
+            let input = crate::examples::input(20, name);
+            assert!(super::check_input(&input).is_empty(), "{name}");
+        }
+        assert_eq!(
+            super::check_input("a -> b\n%b -> broadcaster\n&b -> a\n%c"),
+            [
+                "Line 1: module a has the wrong type",
+                "Line 2: module b sends pulses to the broadcaster",
+                "Line 3: module b is defined again",
+                "Line 4: column 3: expected Tag, found the end of input",
+                "There is no broadcaster",
+            ]
+        );
     }
 }
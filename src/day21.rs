@@ -1,35 +1,103 @@
-use std::collections::HashSet;
+use std::collections::{HashSet, VecDeque};
 
-use crate::common::Coord;
+use crate::common::{validate, Coord, Grid, Neighborhood};
 
-pub fn run() -> anyhow::Result<()> {
-    let input = std::fs::read_to_string("day21.txt")?;
+#[derive(serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Day {
+    pub part1_steps: usize,
+    pub part2_steps: usize,
+}
 
-    let a:isize = -1;
-    println!("a {}", a % 10);
+impl Day {
+    pub const DEFAULT: Self = Self {
+        part1_steps: 64,
+        part2_steps: 26_501_365,
+    };
+}
 
-    println!("21:1 - {}", run_1(&input)?);
-    println!("21:2 - {}", run_2(&input)?);
+impl Default for Day {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
 
-    Ok(())
+impl crate::solver::Solver for Day {
+    fn day(&self) -> usize {
+        21
+    }
+
+    fn title(&self) -> &'static str {
+        "Step Counter"
+    }
+
+    fn part1(&self, input: &str) -> anyhow::Result<crate::answer::Answer> {
+        Ok(simulate(input, self.part1_steps)?.into())
+    }
+
+    fn part2(&self, input: &str) -> anyhow::Result<crate::answer::Answer> {
+        Ok(simulate_2(input, self.part2_steps)?.into())
+    }
+
+    fn validate(&self, input: &str) -> Option<Vec<String>> {
+        Some(check_input(input))
+    }
 }
 
-fn parse(i: &str) -> (Coord, HashSet<Coord>) {
-    let mut start = None;
-    let mut garden_plots = HashSet::new();
-    i.lines().enumerate().for_each(|(row, line)| {
-        line.chars().enumerate().for_each(|(col, c)| match c {
-            '.' => {
-                garden_plots.insert((row, col).into());
-            }
-            'S' => {
-                garden_plots.insert((row, col).into());
-                start = Some((row, col).into())
-            }
-            _ => (),
-        })
-    });
-    (start.unwrap(), garden_plots)
+/// The start and which tiles are garden plots.
+fn parse(i: &str) -> anyhow::Result<(Coord, Grid<bool>)> {
+    let map: Grid<char> = i.parse()?;
+    let start = map
+        .position(|c| *c == 'S')
+        .ok_or_else(|| anyhow::anyhow!("No start tile 'S' in the map"))?;
+    Ok((start, map.map(|c| matches!(c, '.' | 'S'))))
+}
+
+/// A square of plots and rocks with an odd side, with the start in the
+/// middle and no rocks in its row or column. The garden repeats, so walks
+/// along those corridors reach the copies in a straight line.
+fn check_input(input: &str) -> Vec<String> {
+    let mut problems = validate::grid(input, ".#S");
+    let start = match validate::unique(input, 'S') {
+        Ok(start) => start,
+        Err(e) => {
+            problems.push(e);
+            return problems;
+        }
+    };
+    let Ok(map) = input.parse::<Grid<char>>() else {
+        return problems;
+    };
+
+    let (rows, cols) = (map.rows(), map.cols());
+    if rows != cols || rows % 2 == 0 {
+        problems.push(format!(
+            "The map is {rows}x{cols}, not a square with an odd side"
+        ));
+    }
+    let centre = Coord::from((rows / 2, cols / 2));
+    if start != centre {
+        problems.push(format!(
+            "The start is at {}, not in the centre at {}",
+            validate::at(start),
+            validate::at(centre)
+        ));
+    }
+    if let Some((row, col)) = start.index() {
+        if map.row(row).contains(&'#') {
+            problems.push(format!(
+                "There are rocks in the start's row, line {}",
+                row + 1
+            ));
+        }
+        if map.column(col).any(|c| *c == '#') {
+            problems.push(format!(
+                "There are rocks in the start's column, column {}",
+                col + 1
+            ));
+        }
+    }
+    problems
 }
 
 //fn print(map: &HashSet<Coord>, overlay: &HashSet<Coord>) {
@@ -52,42 +120,40 @@ fn parse(i: &str) -> (Coord, HashSet<Coord>) {
 //    }
 //}
 
-fn simulate(i: &str, steps: usize) -> anyhow::Result<usize> {
-    let (start, garden_plots) = parse(i);
-    let mut visited = HashSet::new();
-    visited.insert(start);
-
-    let mut this_round = HashSet::new();
-    this_round.insert(start);
-
-    for _ in 0..steps {
-        let mut next_round = HashSet::new();
-        for c in this_round {
-            let nbrs: Vec<_> = c
-                .neighbors()
-                .filter(
-                    |c| garden_plots.contains(c), //&& !visited.contains(c)
-                )
-                .collect();
-
-            nbrs.iter().for_each(|c| {
-                visited.insert(*c);
-                next_round.insert(*c);
-            });
+/// Shortest walk from `start` to every reachable plot.
+fn distances(garden_plots: &Grid<bool>, start: Coord) -> Grid<Option<usize>> {
+    let mut dist = garden_plots.map(|_| None);
+    dist[start] = Some(0);
+    let mut to_visit = VecDeque::from([start]);
+    while let Some(c) = to_visit.pop_front() {
+        let d = dist[c].unwrap_or_default();
+        for n in garden_plots.neighbors(c) {
+            if garden_plots[n] && dist[n].is_none() {
+                dist[n] = Some(d + 1);
+                to_visit.push_back(n);
+            }
         }
-
-        this_round = next_round;
     }
-
-    Ok(this_round.len())
+    dist
 }
 
-fn run_1(input: &str) -> anyhow::Result<usize> {
-    simulate(input, 64)
+/// Plots that can be the last of a walk of exactly `steps`: those reachable
+/// in at most that many steps, with the same parity since the remaining steps
+/// can be spent going back and forth.
+fn simulate(i: &str, steps: usize) -> anyhow::Result<usize> {
+    let (start, garden_plots) = parse(i)?;
+    let dist = distances(&garden_plots, start);
+
+    // Nothing outside the Manhattan radius can be reached
+    Ok(start
+        .around(Neighborhood::Manhattan(steps), Some(garden_plots.bounds()))
+        .chain([start])
+        .filter(|c| dist[*c].is_some_and(|d| d <= steps && d % 2 == steps % 2))
+        .count())
 }
 
 fn simulate_2(i: &str, steps: usize) -> anyhow::Result<usize> {
-    let (start, garden_plots) = parse(i);
+    let (start, garden_plots) = parse(i)?;
     let mut visited = HashSet::new();
     visited.insert(start);
 
@@ -97,10 +163,10 @@ fn simulate_2(i: &str, steps: usize) -> anyhow::Result<usize> {
     for _ in 0..steps {
         let mut next_round = HashSet::new();
         for c in this_round {
-            let nbrs: Vec<_> = c
-                .neighbors()
+            let nbrs: Vec<_> = garden_plots
+                .neighbors(c)
                 .filter(
-                    |c| garden_plots.contains(c), //&& !visited.contains(c)
+                    |c| garden_plots[*c], //&& !visited.contains(c)
                 )
                 .collect();
 
@@ -113,44 +179,50 @@ fn simulate_2(i: &str, steps: usize) -> anyhow::Result<usize> {
         this_round = next_round;
     }
 
-    dbg! {&this_round};
+    debug!("{this_round:?}");
     Ok(this_round.len())
 }
 
-fn run_2(input: &str) -> anyhow::Result<usize> {
-    simulate_2(input, 26_501_365)
-}
-
 #[cfg(test)]
 mod tests {
-    const INPUT: &str = "...........
-.....###.#.
-.###.##..#.
-..#.#...#..
-....#.#....
-.##..S####.
-.##..#...#.
-.......##..
-.##.#.####.
-.##..##.##.
-...........";
-
     #[test]
     fn day21_run_1() {
-        assert_eq!(super::simulate(INPUT, 1).unwrap(), 2);
-        assert_eq!(super::simulate(INPUT, 2).unwrap(), 4);
-        assert_eq!(super::simulate(INPUT, 3).unwrap(), 6);
-        assert_eq!(super::simulate(INPUT, 6).unwrap(), 16);
+        let input = crate::examples::input(21, "example");
+        assert_eq!(super::simulate(&input, 1).unwrap(), 2);
+        assert_eq!(super::simulate(&input, 2).unwrap(), 4);
+        assert_eq!(super::simulate(&input, 3).unwrap(), 6);
+        // Far more steps than the map is wide only costs the map's size
+        assert_eq!(
+            super::simulate(&input, 1_000_000).unwrap(),
+            super::simulate(&input, 1_000_002).unwrap()
+        );
     }
 
     #[test]
-    fn day21_run_2() {
-        assert_eq!(super::simulate_2(INPUT, 6).unwrap(), 16);
-        //assert_eq!(super::simulate_2(INPUT, 10).unwrap(), 50);
-        //assert_eq!(super::simulate_2(INPUT, 50).unwrap(), 1594);
-        //assert_eq!(super::simulate_2(INPUT, 100).unwrap(), 6536);
-        //assert_eq!(super::simulate_2(INPUT, 500).unwrap(), 167004);
-        //assert_eq!(super::simulate_2(INPUT, 1000).unwrap(), 668697);
-        //assert_eq!(super::simulate_2(INPUT, 5000).unwrap(), 16733044);
+    fn day21_validate() {
+        // The example has no clear corridors, unlike the real inputs
+        assert_eq!(
+            super::check_input(&crate::examples::input(21, "example")),
+            [
+                "There are rocks in the start's row, line 6",
+                "There are rocks in the start's column, column 6",
+            ]
+        );
+        assert!(super::check_input("...\n.S.\n...").is_empty());
+        assert_eq!(
+            super::check_input("S..\n...\n#.."),
+            [
+                "The start is at line 1, column 1, not in the centre at line 2, column 2",
+                "There are rocks in the start's column, column 1",
+            ]
+        );
+        assert_eq!(
+            super::check_input("S.\n.."),
+            [
+                "The map is 2x2, not a square with an odd side",
+                "The start is at line 1, column 1, not in the centre at line 2, column 2",
+            ]
+        );
+        assert_eq!(super::check_input("..."), ["There is no 'S'"]);
     }
 }
@@ -1,27 +1,55 @@
-pub fn run() -> anyhow::Result<()> {
-    let input = std::fs::read_to_string("day22.txt")?;
+use crate::common::validate;
 
-    println!("22:1 - {}", run_1(&input)?);
-    println!("22:2 - {}", run_2(&input)?);
+pub struct Day;
 
-    Ok(())
-}
+impl crate::solver::Solver for Day {
+    fn day(&self) -> usize {
+        22
+    }
 
-fn run_1(_input: &str) -> anyhow::Result<usize> {
-  todo!()
-}
+    fn title(&self) -> &'static str {
+        "Sand Slabs"
+    }
 
-fn run_2(_input: &str) -> anyhow::Result<usize> {
-  todo!()
-}
+    fn part1(&self, input: &str) -> anyhow::Result<crate::answer::Answer> {
+        Ok(run_1(input)?.into())
+    }
 
-#[cfg(test)]
-mod tests {
-    #[test]
-    fn day22_run_1() {
+    fn part2(&self, input: &str) -> anyhow::Result<crate::answer::Answer> {
+        Ok(run_2(input)?.into())
     }
 
-    #[test]
-    fn day22_run_2() {
+    fn validate(&self, input: &str) -> Option<Vec<String>> {
+        Some(check_input(input))
     }
 }
+
+/// Each line is a brick given by the coordinates of two opposite ends, the
+/// first of them the smaller on every axis.
+fn check_input(input: &str) -> Vec<String> {
+    validate::lines(input, |line| {
+        let ends: Vec<Vec<u32>> = line
+            .split('~')
+            .filter_map(|end| end.split(',').map(|v| v.parse().ok()).collect())
+            .filter(|end: &Vec<u32>| end.len() == 3)
+            .collect();
+        match &ends[..] {
+            [a, b] if line.matches('~').count() == 1 => {
+                if a.iter().zip(b).any(|(a, b)| a > b) {
+                    vec!["the first end is past the second".to_string()]
+                } else {
+                    vec![]
+                }
+            }
+            _ => vec![format!("expected a brick like 1,0,1~1,2,1, found {line:?}")],
+        }
+    })
+}
+
+fn run_1(_input: &str) -> anyhow::Result<usize> {
+    Err(crate::solver::NotImplemented.into())
+}
+
+fn run_2(_input: &str) -> anyhow::Result<usize> {
+    Err(crate::solver::NotImplemented.into())
+}
@@ -1,133 +1,134 @@
-use crate::common::Coord;
+use crate::common::{graph, validate, Coord, Dir, Grid, Neighborhood, ORTHOGONAL};
 
-pub fn run() -> anyhow::Result<()> {
-    let input = std::fs::read_to_string("day23.txt")?;
+pub struct Day;
 
-    println!("23:1 - {}", run_1(&input)?);
-    println!("23:2 - {}", run_2(&input)?);
+impl crate::solver::Solver for Day {
+    fn day(&self) -> usize {
+        23
+    }
+
+    fn title(&self) -> &'static str {
+        "A Long Walk"
+    }
 
-    Ok(())
+    fn part1(&self, input: &str) -> anyhow::Result<crate::answer::Answer> {
+        Ok(run_1(input)?.into())
+    }
+
+    fn part2(&self, input: &str) -> anyhow::Result<crate::answer::Answer> {
+        Ok(run_2(input)?.into())
+    }
+
+    fn validate(&self, input: &str) -> Option<Vec<String>> {
+        Some(check_input(input))
+    }
 }
 
 #[derive(PartialEq, Eq)]
 enum MapItem {
     Forrest,
     Path,
-    SlopeN,
-    SlopeE,
-    SlopeS,
-    SlopeW,
+    Slope(Dir),
 }
 
-type Map = Vec<Vec<MapItem>>;
-
-fn parse(i: &str) -> Map {
-    i.lines()
-        .map(|line| {
-            line.chars()
-                .map(|c| match c {
-                    '.' => MapItem::Path,
-                    '#' => MapItem::Forrest,
-                    '>' => MapItem::SlopeE,
-                    '<' => MapItem::SlopeW,
-                    '^' => MapItem::SlopeN,
-                    'v' => MapItem::SlopeS,
-                    _ => unreachable!(),
-                })
-                .collect()
+type Map = Grid<MapItem>;
+
+fn parse(i: &str) -> anyhow::Result<Map> {
+    Grid::parse(i, |c| {
+        Ok(match c {
+            '.' => MapItem::Path,
+            '#' => MapItem::Forrest,
+            _ => MapItem::Slope(
+                Dir::from_arrow(c).ok_or_else(|| anyhow::anyhow!("Invalid tile '{c}'"))?,
+            ),
         })
+    })
+}
+
+/// Paths, forest and slopes, with a single path tile on the top and the
+/// bottom row to start and finish on.
+fn check_input(input: &str) -> Vec<String> {
+    let mut problems = validate::grid(input, ".#^>v<");
+    let rows: Vec<&str> = input.lines().collect();
+    if let [first, .., last] = rows[..] {
+        for (name, row) in [("top", first), ("bottom", last)] {
+            let paths = row.matches('.').count();
+            if paths != 1 {
+                problems.push(format!("The {name} row has {paths} path tiles, expected 1"));
+            }
+        }
+    }
+    problems
+}
+
+/// Where one step from `c` can go, only downhill on slopes if `slippery`.
+fn moves(map: &Map, c: Coord, slippery: bool) -> Vec<Coord> {
+    let slope;
+    let stencil: &[Coord] = match map[c] {
+        MapItem::Forrest => return vec![],
+        MapItem::Slope(dir) if slippery => {
+            slope = [dir.movement()];
+            &slope
+        }
+        _ => &ORTHOGONAL,
+    };
+    c.around(Neighborhood::Offsets(stencil), Some(map.bounds()))
+        .filter(|n| map[*n] != MapItem::Forrest)
         .collect()
 }
 
-fn can_go(map: &Map, c: Coord) -> bool {
-    c.row() < map.len() && c.col() < map[c.row()].len() && map[c.row()][c.col()] != MapItem::Forrest
+/// The path tile on `row`.
+fn entrance(map: &Map, row: usize) -> anyhow::Result<Coord> {
+    let col = (row < map.rows())
+        .then(|| map.row(row).iter().position(|i| *i == MapItem::Path))
+        .flatten()
+        .ok_or_else(|| anyhow::anyhow!("No path tile on row {}", row + 1))?;
+    Ok((row, col).into())
+}
+
+fn longest_hike(input: &str, slippery: bool) -> anyhow::Result<usize> {
+    let map = parse(input)?;
+    let start = entrance(&map, 0)?;
+    let finish = entrance(&map, map.rows().saturating_sub(1))?;
+
+    let junctions = graph::compress_grid(&map, &[start, finish], |c| moves(&map, c, slippery));
+    debug!("{}", graph::to_dot(&junctions));
+
+    let node = |c: Coord| junctions.node_indices().find(|i| junctions[*i] == c);
+    node(start)
+        .zip(node(finish))
+        .and_then(|(start, finish)| graph::longest_path(&junctions, start, finish))
+        .ok_or_else(|| anyhow::anyhow!("No path to {finish}"))
 }
 
 fn run_1(input: &str) -> anyhow::Result<usize> {
-    let map = parse(input);
-
-    let start_pos: Coord = (0, 1).into();
-    let finish_col = map[map.len() - 1]
-        .iter()
-        .enumerate()
-        .find(|(_, i)| **i == MapItem::Path)
-        .unwrap()
-        .0;
-
-    let finish_pos: Coord = (map.len() - 1, finish_col).into();
-
-    let (longest_path, _cost) = pathfinding::directed::astar::astar(
-        &start_pos,
-        |pos| {
-            println!("successors");
-            // return next
-            let nbrs: Vec<Coord> = match map[pos.row()][pos.col()] {
-                MapItem::Path => pos.neighbors().filter(|n| can_go(&map, *n)).collect(),
-                MapItem::SlopeN => [*pos + (-1, 0).into()]
-                    .into_iter()
-                    .filter(|n| can_go(&map, *n))
-                    .collect(),
-                MapItem::SlopeE => [*pos + (0, 1).into()]
-                    .into_iter()
-                    .filter(|n| can_go(&map, *n))
-                    .collect(),
-                MapItem::SlopeS => [*pos + (1, 0).into()]
-                    .into_iter()
-                    .filter(|n| can_go(&map, *n))
-                    .collect(),
-                MapItem::SlopeW => [*pos + (0, -1).into()]
-                    .into_iter()
-                    .filter(|n| can_go(&map, *n))
-                    .collect(),
-                MapItem::Forrest => unreachable!(),
-            };
-            nbrs.into_iter().map(|n| (n, -1isize))
-        },
-        |pos| pos.manhattan(&finish_pos) as isize,
-        |pos| *pos == finish_pos,
-    ).unwrap();
-
-    //dbg!{longest_path};
-    Ok(longest_path.len())
+    longest_hike(input, true)
 }
 
-fn run_2(_input: &str) -> anyhow::Result<usize> {
-    todo!()
+fn run_2(input: &str) -> anyhow::Result<usize> {
+    longest_hike(input, false)
 }
 
 #[cfg(test)]
 mod tests {
-
-    const INPUT: &str = "#.#####################
-#.......#########...###
-#######.#########.#.###
-###.....#.>.>.###.#.###
-###v#####.#v#.###.#.###
-###.>...#.#.#.....#...#
-###v###.#.#.#########.#
-###...#.#.#.......#...#
-#####.#.#.#######.#.###
-#.....#.#.#.......#...#
-#.#####.#.#.#########v#
-#.#...#...#...###...>.#
-#.#.#v#######v###.###v#
-#...#.>.#...>.>.#.###.#
-#####v#.#.###v#.#.###.#
-#.....#...#...#.#.#...#
-#.#########.###.#.#.###
-#...###...#...#...#.###
-###.###.#.###v#####v###
-#...#...#.#.>.>.#.>.###
-#.###.###.#.###.#.#v###
-#.....###...###...#...#
-#####################.#";
-
     #[test]
-    #[ignore]
-    fn day23_run_1() {
-        assert_eq!(super::run_1(INPUT).unwrap(), 94);
+    fn day23_validate() {
+        assert!(super::check_input(&crate::examples::input(23, "example")).is_empty());
+        assert_eq!(
+            super::check_input("#.#\n#L#\n.#."),
+            [
+                "Unexpected 'L' at line 2, column 2",
+                "The bottom row has 2 path tiles, expected 1",
+            ]
+        );
     }
 
     #[test]
-    fn day23_run_2() {}
+    fn day23_invalid_tile() {
+        for tile in ['L', 'N', '3'] {
+            let input = format!("#.#\n#{tile}#\n#.#");
+            let e = super::run_1(&input).unwrap_err().to_string();
+            assert_eq!(e, format!("Invalid tile {tile:?} at row 2, col 2"));
+        }
+    }
 }
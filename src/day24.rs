@@ -1,107 +1,105 @@
-use nom::sequence::separated_pair;
+use crate::common::{parse, validate, Coord3};
 
-pub fn run() -> anyhow::Result<()> {
-    let input = std::fs::read_to_string("day24.txt")?;
-
-    println!(
-        "24:1 - {}",
-        run_1(&input, 200000000000000.0, 400000000000000.0)?
-    );
-    println!("24:2 - {}", run_2(&input)?);
-
-    Ok(())
+#[derive(serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Day {
+    /// Where the part 1 paths have to cross, on both x and y
+    pub test_area: std::ops::RangeInclusive<f64>,
 }
 
-#[derive(Debug)]
-struct Coord3 {
-    x: f64,
-    y: f64,
-    _z: f64,
+impl Day {
+    pub const DEFAULT: Self = Self {
+        test_area: 200000000000000.0..=400000000000000.0,
+    };
 }
 
-impl From<(i64, i64, i64)> for Coord3 {
-    fn from((x, y, z): (i64, i64, i64)) -> Self {
-        Self {
-            x: x as f64,
-            y: y as f64,
-            _z: z as f64,
-        }
+impl Default for Day {
+    fn default() -> Self {
+        Self::DEFAULT
     }
 }
 
-impl From<(f64, f64, f64)> for Coord3 {
-    fn from((x, y, z): (f64, f64, f64)) -> Self {
-        Self { x, y, _z: z }
+impl crate::solver::Solver for Day {
+    fn day(&self) -> usize {
+        24
+    }
+
+    fn title(&self) -> &'static str {
+        "Never Tell Me The Odds"
+    }
+
+    fn part1(&self, input: &str) -> anyhow::Result<crate::answer::Answer> {
+        Ok(run_1(input, *self.test_area.start(), *self.test_area.end())?.into())
+    }
+
+    fn part2(&self, input: &str) -> anyhow::Result<crate::answer::Answer> {
+        Ok(run_2(input)?.into())
+    }
+
+    fn validate(&self, input: &str) -> Option<Vec<String>> {
+        Some(check_input(input))
     }
 }
 
-//#[derive(Debug)]
-//struct Coord2 {
-//    x: f64,
-//    y: f64,
-//}
-//
-//impl From<Coord3> for Coord {
-//    fn from(c: Coord3) -> Self {
-//        (c.x as i32, c.y as i32).into()
-//    }
-//}
-
-fn parse_line(i: crate::Input) -> crate::PResult<(Coord3, Coord3)> {
-    let comma_sep = |i| {
-        let (i, _) = nom::bytes::complete::tag(",")(i)?;
-        let (i, _) = nom::character::complete::space0(i)?;
-        Ok((i, ()))
-    };
-    let (i, (px, (py, pz))) = separated_pair(
-        nom::character::complete::i64,
-        comma_sep,
-        separated_pair(
-            nom::character::complete::i64,
-            comma_sep,
-            nom::character::complete::i64,
-        ),
-    )(i)?;
+type Hailstone = (Coord3<f64>, Coord3<f64>);
+
+fn parse_coord(i: crate::Input) -> crate::PResult<Coord3<f64>> {
+    nom::combinator::map_opt(parse::ints, |v: Vec<i64>| match v[..] {
+        [x, y, z] => Coord3::new(x, y, z).cast(),
+        _ => None,
+    })(i)
+}
+
+fn parse_line(i: crate::Input) -> crate::PResult<Hailstone> {
+    let (i, p) = parse_coord(i)?;
     let (i, _) = nom::character::complete::space0(i)?;
     let (i, _) = nom::bytes::complete::tag("@")(i)?;
     let (i, _) = nom::character::complete::space0(i)?;
-    let (i, (vx, (vy, vz))) = separated_pair(
-        nom::character::complete::i64,
-        comma_sep,
-        separated_pair(
-            nom::character::complete::i64,
-            comma_sep,
-            nom::character::complete::i64,
-        ),
-    )(i)?;
+    let (i, v) = parse_coord(i)?;
 
-    Ok((i, ((px, py, pz).into(), (vx, vy, vz).into())))
+    Ok((i, (p, v)))
 }
 
-fn parse(i: crate::Input) -> crate::PResult<Vec<(Coord3, Coord3)>> {
-    let (i, res) = nom::multi::separated_list1(nom::character::complete::newline, parse_line)(i)?;
+fn parse(i: crate::Input) -> crate::PResult<Vec<Hailstone>> {
+    let (i, res) = nom::multi::separated_list1(
+        nom::character::complete::newline,
+        nom::error::context("hailstone", parse_line),
+    )(i)?;
     Ok((i, res))
 }
 
+/// Each line is a hailstone that moves on the x-y plane, where part 1
+/// follows its path.
+fn check_input(input: &str) -> Vec<String> {
+    validate::lines(input, |line| match validate::parses(line, parse_line) {
+        Ok((_, v)) if v.x == 0.0 && v.y == 0.0 => {
+            vec!["the hailstone doesn't move on x and y".to_string()]
+        }
+        Ok(_) => vec![],
+        Err(e) => vec![e],
+    })
+}
+
 //p1 + v1*t1 = p2 + v2*t2
 // |x1| - |x2| = |-v1x  v2x||t1|
 // |y1| - |y2| = |-v1y  v2y||t2|
 //
-fn solve2((p1, v1): &(Coord3, Coord3), (p2, v2): &(Coord3, Coord3)) -> Option<Coord3> {
+fn solve2((p1, v1): &Hailstone, (p2, v2): &Hailstone) -> Option<Coord3<f64>> {
     let a = nalgebra::Matrix2::new(-v1.x, v2.x, -v1.y, v2.y);
-    let b = nalgebra::Matrix2x1::new(p1.x - p2.x, p1.y - p2.y);
+    let b = nalgebra::Vector3::from(*p1 - *p2).xy();
     let c = a.try_inverse()? * b;
     let t1 = c[0];
     let t2 = c[1];
     if t1 >= 0.0 && t2 >= 0.0 {
-        Some((p1.x + t1 * v1.x, p1.y + t1 * v1.y, 0.0).into())
+        // Only x and y take part, z is wherever the first one is then
+        Some(*p1 + *v1 * t1)
     } else {
         None
     }
 }
 
 fn run_1(input: &str, min: f64, max: f64) -> anyhow::Result<usize> {
-    let (_, lines) = parse(input).map_err(|e| anyhow::anyhow!("{e}"))?;
+    let lines = parse::parse_complete(input, parse)?;
 
     let mut cnt = 0;
     for a in 0..(lines.len() - 1) {
@@ -118,16 +116,26 @@ fn run_1(input: &str, min: f64, max: f64) -> anyhow::Result<usize> {
 }
 
 fn run_2(_input: &str) -> anyhow::Result<usize> {
-    todo!()
+    Err(crate::solver::NotImplemented.into())
 }
 
 #[cfg(test)]
 mod tests {
-    const INPUT: &str = "19, 13, 30 @ -2,  1, -2
-18, 19, 22 @ -1, -1, -2
-20, 25, 34 @ -2, -2, -4
-12, 31, 28 @ -1, -2, -1
-20, 19, 15 @  1, -5, -3";
+    fn hail(i: &str) -> super::Hailstone {
+        super::parse_line(i).unwrap().1
+    }
+
+    #[test]
+    fn day24_validate() {
+        assert!(super::check_input(&crate::examples::input(24, "example")).is_empty());
+        assert_eq!(
+            super::check_input("19, 13, 30 @ 0, 0, -2\n18, 19, 22 # -1, -1, -2"),
+            [
+                "Line 1: the hailstone doesn't move on x and y",
+                "Line 2: column 12: expected Tag, found '#'",
+            ]
+        );
+    }
 
     #[test]
     fn day24_intersect() {
@@ -144,27 +152,19 @@ mod tests {
         //assert!((i.y - 16.667).abs() < 0.5);
 
         let i = super::solve2(
-            &((19, 13, 30).into(), (-2, 1, -2).into()),
-            &((12, 31, 28).into(), (-1, -2, -1).into()),
+            &hail("19, 13, 30 @ -2, 1, -2"),
+            &hail("12, 31, 28 @ -1, -2, -1"),
         )
         .unwrap();
         assert!((i.x - 6.2).abs() < 0.5);
         assert!((i.y - 19.4).abs() < 0.5);
 
         let i = super::solve2(
-            &((18, 19, 22).into(), (-1, -1, -2).into()),
-            &((12, 31, 28).into(), (-1, -2, -1).into()),
+            &hail("18, 19, 22 @ -1, -1, -2"),
+            &hail("12, 31, 28 @ -1, -2, -1"),
         )
         .unwrap();
         assert!((i.x - -6.0).abs() < 0.5);
         assert!((i.y - -5.0).abs() < 0.5);
     }
-
-    #[test]
-    fn day24_run_1() {
-        assert_eq!(super::run_1(INPUT, 7.0, 27.0).unwrap(), 2);
-    }
-
-    #[test]
-    fn day24_run_2() {}
 }
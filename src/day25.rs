@@ -1,62 +1,85 @@
+use nom::{
+    character::complete::{alphanumeric1, newline, space1},
+    multi::separated_list1,
+};
 
-pub fn run() -> anyhow::Result<()> {
-    let input = std::fs::read_to_string("day25.txt")?;
+use crate::common::{
+    graph::{self, GraphBuilder},
+    parse, validate,
+};
 
-    println!("25:1 - {}", run_1(&input)?);
-    println!("25:2 - {}", run_2(&input)?);
+pub struct Day;
 
-    Ok(())
+impl crate::solver::Solver for Day {
+    fn day(&self) -> usize {
+        25
+    }
+
+    fn title(&self) -> &'static str {
+        "Snowverload"
+    }
+
+    fn part1(&self, input: &str) -> anyhow::Result<crate::answer::Answer> {
+        Ok(run_1(input)?.into())
+    }
+
+    fn part2(&self, input: &str) -> anyhow::Result<crate::answer::Answer> {
+        Ok(run_2(input)?.into())
+    }
+
+    fn validate(&self, input: &str) -> Option<Vec<String>> {
+        Some(check_input(input))
+    }
 }
 
 fn run_1(input: &str) -> anyhow::Result<usize> {
-    let graph = parse(input);
-    // dbg!{&graph};
-    println!("{:?}", petgraph::dot::Dot::with_config(&graph, &[petgraph::dot::Config::EdgeNoLabel]));
-    todo!()
+    let graph = parse(input)?;
+    debug!("{}", graph::to_dot(&graph));
+
+    let (cut, side) =
+        graph::min_cut(&graph).ok_or_else(|| anyhow::anyhow!("Need at least two nodes"))?;
+    if cut != 3 {
+        anyhow::bail!("The smallest cut has {cut} wires, not 3");
+    }
+    Ok(side.len() * (graph.node_count() - side.len()))
 }
 
 fn run_2(_input: &str) -> anyhow::Result<usize> {
-    todo!()
+    Err(crate::solver::NotImplemented.into())
 }
 
-fn parse(i: &str) -> petgraph::graph::UnGraph<String, ()> {
-    let mut res = petgraph::graph::UnGraph::new_undirected();
-    for line in i.lines() {
-        let mut colon_s = line.split(':');
-        let n1 = colon_s.next().unwrap();
-        let i1 = res.node_indices().find(|n| res[*n]==n1).unwrap_or_else(|| res.add_node(n1.to_string()));
-        let nbrs = colon_s.next().unwrap().split(' ');
-        nbrs.filter(|s| !s.is_empty()).for_each(|n2| {
-            let i2 = res.node_indices().find(|n| res[*n]==n2).unwrap_or_else(|| res.add_node(n2.to_string()));
-            res.add_edge(i1, i2, ());
-        });
-    }
-
-    res
+/// A component and the ones it is wired to, like `jqt: rhn xhk nvd`.
+fn parse_component<'a>(i: crate::Input<'a>) -> crate::PResult<'a, (&'a str, Vec<&'a str>)> {
+    parse::key_values(separated_list1(space1, alphanumeric1))(i)
 }
 
-#[cfg(test)]
-mod tests {
-    const INPUT: &str = "jqt: rhn xhk nvd
-rsh: frs pzl lsr
-xhk: hfx
-cmg: qnr nvd lhk bvb
-rhn: xhk bvb hfx
-bvb: xhk hfx
-pzl: lsr hfx nvd
-qnr: nvd
-ntq: jqt hfx bvb xhk
-nvd: lhk
-lsr: lhk
-rzs: qnr cmg lsr rsh
-frs: qnr lhk lsr";
-
-    #[test]
-    #[ignore]
-    fn day25_run_1() {
-        assert_eq!(super::run_1(INPUT).unwrap(), 54);
-    }
+/// Each line is a component wired to others, but not to itself.
+fn check_input(input: &str) -> Vec<String> {
+    validate::lines(input, |line| {
+        let looped = validate::parses(line, |i| {
+            let (i, (name, wires)) = parse_component(i)?;
+            Ok((i, wires.contains(&name).then(|| name.to_string())))
+        });
+        match looped {
+            Ok(Some(name)) => vec![format!("component {name} is wired to itself")],
+            Ok(None) => vec![],
+            Err(e) => vec![e],
+        }
+    })
+}
 
-    #[test]
-    fn day25_run_2() {}
+fn parse(input: &str) -> anyhow::Result<petgraph::graph::UnGraph<String, usize>> {
+    let graph = parse::parse_complete(input, |i| {
+        let component = nom::error::context("component", parse_component);
+        nom::combinator::map(separated_list1(newline, component), |components| {
+            let mut res = GraphBuilder::default();
+            for (n1, nbrs) in components {
+                for n2 in nbrs {
+                    res.edge(n1, n2, 1);
+                }
+            }
+            res.build()
+        })(i)
+    })?;
+    Ok(graph)
 }
@@ -1,107 +1,91 @@
 use std::collections::{HashMap, HashSet};
 
-pub fn run() -> anyhow::Result<()> {
-    let input = std::fs::read_to_string("day3.txt")?;
+use crate::common::{validate, Coord, Grid};
 
-    println!("3:1 - {}", run_1(&input)?);
-    println!("3:2 - {}", run_2(&input)?);
+pub struct Day;
 
-    Ok(())
-}
+impl crate::solver::Solver for Day {
+    fn day(&self) -> usize {
+        3
+    }
 
-fn run_1(input: &str) -> anyhow::Result<usize> {
-    let input: Vec<&str> = input.lines().collect();
-    // Find the symbols
-    let mut symbol_coords = HashSet::new();
-    // Start of digits
-    let mut number_start_coords = HashSet::new();
+    fn title(&self) -> &'static str {
+        "Gear Ratios"
+    }
 
-    for (row, line) in input.iter().enumerate() {
-        let mut last_was_digit = false;
-        for (col, c) in line.chars().enumerate() {
-            if c == '.' {
-                last_was_digit = false;
-            } else if c.is_ascii_digit() {
-                if !last_was_digit {
-                    number_start_coords.insert((row, col));
-                    last_was_digit = true;
-                }
-            } else {
-                last_was_digit = false;
-                symbol_coords.insert((row, col));
-            }
-        }
+    fn part1(&self, input: &str) -> anyhow::Result<crate::answer::Answer> {
+        Ok(run_1(input)?.into())
+    }
+
+    fn part2(&self, input: &str) -> anyhow::Result<crate::answer::Answer> {
+        Ok(run_2(input)?.into())
     }
 
+    fn validate(&self, input: &str) -> Option<Vec<String>> {
+        Some(check_input(input))
+    }
+}
+
+/// The part numbers in the schematic, with the coordinates of their digits.
+fn numbers(grid: &Grid<char>) -> anyhow::Result<Vec<(usize, Vec<Coord>)>> {
     let mut res = Vec::new();
-    for (row, col) in number_start_coords {
-        let end = input[row][col..]
-            .chars()
-            .position(|c| !c.is_ascii_digit())
-            .map(|c| c + col)
-            .unwrap_or(input[row].len());
-        let num: usize = input[row][col..end].parse()?;
-        let start_row = row.max(1) - 1;
-        let end_row = row.min(input.len() - 2) + 1;
-        #[allow(clippy::needless_range_loop)]
-        'outer: for r in start_row..=end_row {
-            let start_col = col.max(1) - 1;
-            let end_col = end.min(input[r].len() - 2);
-            for c in start_col..=end_col {
-                if symbol_coords.contains(&(r, c)) {
-                    res.push(num);
-                    break 'outer;
-                }
+    for (row, line) in grid.iter_rows().enumerate() {
+        let mut col = 0;
+        while col < line.len() {
+            if !line[col].is_ascii_digit() {
+                col += 1;
+                continue;
             }
+            let end = line[col..]
+                .iter()
+                .position(|c| !c.is_ascii_digit())
+                .map_or(line.len(), |p| p + col);
+            let num = line[col..end].iter().collect::<String>().parse()?;
+            res.push((num, (col..end).map(|c| (row, c).into()).collect()));
+            col = end;
         }
     }
-    Ok(res.iter().sum())
+    Ok(res)
 }
 
-fn run_2(input: &str) -> anyhow::Result<usize> {
-    let input: Vec<&str> = input.lines().collect();
-    // Find the symbols
-    let mut star_symbol_coords = HashSet::new();
-    // Start of digits
-    let mut number_start_coords = HashSet::new();
+/// The cells around `digits`, diagonals included, that are inside the grid.
+fn adjacent(grid: &Grid<char>, digits: &[Coord]) -> HashSet<Coord> {
+    digits
+        .iter()
+        .flat_map(|d| grid.neighbors8(*d))
+        .filter(|c| !digits.contains(c))
+        .collect()
+}
 
-    for (row, line) in input.iter().enumerate() {
-        let mut last_was_digit = false;
-        for (col, c) in line.chars().enumerate() {
-            if c == '.' {
-                last_was_digit = false;
-            } else if c == '*' {
-                star_symbol_coords.insert((row, col));
-                last_was_digit = false;
-            } else if c.is_ascii_digit() {
-                if !last_was_digit {
-                    number_start_coords.insert((row, col));
-                    last_was_digit = true;
-                }
-            } else {
-                last_was_digit = false;
-            }
+/// A grid of digits, `.` and symbols, which are any other punctuation.
+fn check_input(input: &str) -> Vec<String> {
+    const TILES: &str = "0123456789.!\"#$%&'()*+,-/:;<=>?@[\\]^_`{|}~";
+    validate::grid(input, TILES)
+}
+
+fn run_1(input: &str) -> anyhow::Result<usize> {
+    let grid: Grid<char> = input.parse()?;
+
+    let mut res = Vec::new();
+    for (num, digits) in numbers(&grid)? {
+        let is_part = adjacent(&grid, &digits)
+            .into_iter()
+            .any(|c| grid[c] != '.' && !grid[c].is_ascii_digit());
+        if is_part {
+            res.push(num);
         }
     }
+    Ok(res.iter().sum())
+}
 
-    let mut res: HashMap<(usize, usize), Vec<usize>> = HashMap::new();
-    for (row, col) in number_start_coords {
-        let end = input[row][col..]
-            .chars()
-            .position(|c| !c.is_ascii_digit())
-            .map(|c| c + col)
-            .unwrap_or(input[row].len());
-        let num: usize = input[row][col..end].parse()?;
-        let start_row = row.max(1) - 1;
-        let end_row = row.min(input.len() - 2) + 1;
-        #[allow(clippy::needless_range_loop)]
-        for r in start_row..=end_row {
-            let start_col = col.max(1) - 1;
-            let end_col = end.min(input[r].len() - 2);
-            for c in start_col..=end_col {
-                if star_symbol_coords.contains(&(r, c)) {
-                    res.entry((r, c)).or_default().push(num);
-                }
+fn run_2(input: &str) -> anyhow::Result<usize> {
+    let grid: Grid<char> = input.parse()?;
+
+    let mut res: HashMap<Coord, Vec<usize>> = HashMap::new();
+    for (num, digits) in numbers(&grid)? {
+        for c in adjacent(&grid, &digits) {
+            if grid[c] == '*' {
+                res.entry(c).or_default().push(num);
             }
         }
     }
@@ -111,27 +95,3 @@ fn run_2(input: &str) -> anyhow::Result<usize> {
         .map(|v| v.iter().product::<usize>())
         .sum())
 }
-
-#[cfg(test)]
-mod tests {
-    const INPUT: &str = "467..114..
-...*......
-..35..633.
-......#...
-617*......
-.....+.58.
-..592.....
-......755.
-...$.*....
-.664.598..";
-
-    #[test]
-    fn day3_run_1() {
-        assert_eq!(super::run_1(INPUT).unwrap(), 4361);
-    }
-
-    #[test]
-    fn day3_run_2() {
-        assert_eq!(super::run_2(INPUT).unwrap(), 467835);
-    }
-}
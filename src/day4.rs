@@ -4,16 +4,34 @@ use nom::{
     bytes::complete::tag,
     character::complete::{newline, space1},
     combinator::map,
+    error::context,
     multi::separated_list1,
 };
 
-pub fn run() -> anyhow::Result<()> {
-    let input = std::fs::read_to_string("day4.txt")?;
+use crate::common::{parse, validate};
 
-    println!("4:1 - {}", run_1(&input)?);
-    println!("4:2 - {}", run_2(&input)?);
+pub struct Day;
 
-    Ok(())
+impl crate::solver::Solver for Day {
+    fn day(&self) -> usize {
+        4
+    }
+
+    fn title(&self) -> &'static str {
+        "Scratchcards"
+    }
+
+    fn part1(&self, input: &str) -> anyhow::Result<crate::answer::Answer> {
+        Ok(run_1(input)?.into())
+    }
+
+    fn part2(&self, input: &str) -> anyhow::Result<crate::answer::Answer> {
+        Ok(run_2(input)?.into())
+    }
+
+    fn validate(&self, input: &str) -> Option<Vec<String>> {
+        Some(check_input(input))
+    }
 }
 
 #[derive(Debug)]
@@ -29,24 +47,38 @@ fn parse_card(i: crate::Input) -> crate::PResult<Card> {
     let (i, id) = nom::character::complete::u64(i)?;
     let (i, _) = tag(":")(i)?;
     let (i, _) = space1(i)?;
-    let (i, winners) = map(
-        separated_list1(space1, nom::character::complete::u64),
-        |w| w.into_iter().collect(),
-    )(i)?;
+    let (i, winners) = map(parse::ints, |w| w.into_iter().collect())(i)?;
     let (i, _) = space1(i)?;
     let (i, _) = tag("|")(i)?;
     let (i, _) = space1(i)?;
-    let (i, nums) = separated_list1(space1, nom::character::complete::u64)(i)?;
+    let (i, nums) = parse::ints(i)?;
 
     Ok((i, Card { id, winners, nums }))
 }
 
 fn parse(i: crate::Input) -> crate::PResult<Vec<Card>> {
-    separated_list1(newline, parse_card)(i)
+    separated_list1(newline, context("card", parse_card))(i)
+}
+
+/// Each line is a card, numbered from 1 in order, since part 2 finds the
+/// cards won by their number.
+fn check_input(input: &str) -> Vec<String> {
+    let mut expected = 1;
+    validate::lines(input, |line| {
+        let problems = match validate::parses(line, parse_card) {
+            Ok(card) if card.id != expected => {
+                vec![format!("card {}, expected {expected}", card.id)]
+            }
+            Ok(_) => vec![],
+            Err(e) => vec![e],
+        };
+        expected += 1;
+        problems
+    })
 }
 
 fn run_1(input: &str) -> anyhow::Result<usize> {
-    let (_, cards) = parse(input).map_err(|e| anyhow::anyhow!(e.to_string()))?;
+    let cards = parse::parse_complete(input, parse)?;
     Ok(cards
         .into_iter()
         .map(|card| {
@@ -62,7 +94,7 @@ fn run_1(input: &str) -> anyhow::Result<usize> {
 }
 
 fn run_2(input: &str) -> anyhow::Result<usize> {
-    let (_, cards) = parse(input).map_err(|e| anyhow::anyhow!(e.to_string()))?;
+    let cards = parse::parse_complete(input, parse)?;
 
     let mut multipliers: HashMap<u64, usize> = HashMap::new();
     for card in cards.iter() {
@@ -83,26 +115,12 @@ fn run_2(input: &str) -> anyhow::Result<usize> {
 
     Ok(multipliers
         .iter()
-        .filter_map(|m| if (*m.0 as usize) <= cards.len() { Some(m.1) } else { None })
+        .filter_map(|m| {
+            if (*m.0 as usize) <= cards.len() {
+                Some(m.1)
+            } else {
+                None
+            }
+        })
         .sum())
 }
-
-#[cfg(test)]
-mod tests {
-    const INPUT: &str = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
-Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
-Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
-Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
-Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
-Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
-
-    #[test]
-    fn day4_run_1() {
-        assert_eq!(super::run_1(INPUT).unwrap(), 13);
-    }
-
-    #[test]
-    fn day4_run_2() {
-        assert_eq!(super::run_2(INPUT).unwrap(), 30);
-    }
-}
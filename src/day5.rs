@@ -1,19 +1,36 @@
-use rayon::prelude::*;
 use std::collections::HashMap;
 
 use nom::{
-    bytes::complete::tag,
-    character::complete::{newline, space1},
-    multi::separated_list1,
+    bytes::complete::tag, character::complete::newline, error::context, multi::separated_list1,
 };
 
-pub fn run() -> anyhow::Result<()> {
-    let input = std::fs::read_to_string("day5.txt")?;
+use crate::common::{
+    intervals::{Interval, IntervalSet, OffsetTable},
+    parse,
+};
+
+pub struct Day;
+
+impl crate::solver::Solver for Day {
+    fn day(&self) -> usize {
+        5
+    }
+
+    fn title(&self) -> &'static str {
+        "If You Give A Seed A Fertilizer"
+    }
+
+    fn part1(&self, input: &str) -> anyhow::Result<crate::answer::Answer> {
+        Ok(run_1(input)?.into())
+    }
 
-    println!("5:1 - {}", run_1(&input)?);
-    println!("5:2 - {}", run_2(&input)?);
+    fn part2(&self, input: &str) -> anyhow::Result<crate::answer::Answer> {
+        Ok(run_2(input)?.into())
+    }
 
-    Ok(())
+    fn validate(&self, input: &str) -> Option<Vec<String>> {
+        Some(check_input(input))
+    }
 }
 
 #[derive(Debug)]
@@ -28,6 +45,17 @@ fn next_dest(maps: &[Map], src_value: usize) -> usize {
         .unwrap_or(src_value)
 }
 
+/// All the maps of one stage as a table over ranges.
+fn offset_table(maps: &[Map]) -> OffsetTable {
+    OffsetTable::new(maps.iter().map(|m| {
+        let src = m.src_range.start as i64..m.src_range.end as i64;
+        (
+            src.into(),
+            m.dest_range.start as i64 - m.src_range.start as i64,
+        )
+    }))
+}
+
 #[derive(Debug)]
 struct Map {
     dest_range: std::ops::Range<usize>,
@@ -41,6 +69,7 @@ impl Map {
             src_range: src..(src + len),
         }
     }
+
     fn to_dest(&self, source: usize) -> Option<usize> {
         if self.src_range.contains(&source) {
             Some(self.dest_range.start + source - self.src_range.start)
@@ -71,28 +100,70 @@ fn parse_maps(i: crate::Input) -> crate::PResult<(String, String, Vec<Map>)> {
     let (i, _) = tag(" map:")(i)?;
     let (i, _) = newline(i)?;
     let (i, maps) = separated_list1(newline, parse_map)(i)?;
-    let (i, _) = nom::combinator::opt(newline)(i)?;
     Ok((i, (from.to_string(), to.to_string(), maps)))
 }
 
 fn parse(i: crate::Input) -> crate::PResult<Garden> {
     // Parse seeds
     let (i, _) = tag("seeds: ")(i)?;
-    let (i, seeds) = separated_list1(
-        space1,
-        nom::combinator::map(nom::character::complete::u32, |v| v as usize),
-    )(i)?;
-    // Eat two newlines
-    let (i, _) = newline(i)?;
-    let (i, _) = newline(i)?;
+    let (i, seeds) = context("seeds", parse::ints)(i)?;
+    let (i, _) = parse::blank_line(i)?;
 
-    let (i, maps) = separated_list1(newline, parse_maps)(i)?;
+    let (i, maps) = parse::blocks(context("map", parse_maps))(i)?;
 
     Ok((i, Garden { seeds, maps }))
 }
 
+/// Seeds in pairs of start and length for part 2, and a chain of maps
+/// from seed to location.
+fn check_input(input: &str) -> Vec<String> {
+    let garden = match parse::parse_complete(input, parse) {
+        Ok(garden) => garden,
+        Err(e) => {
+            return vec![format!(
+                "Line {}, column {}: {}",
+                e.line, e.column, e.message
+            )]
+        }
+    };
+
+    let mut problems = vec![];
+    if garden.seeds.len() % 2 != 0 {
+        problems.push(format!(
+            "There are {} seeds, expected pairs of start and length",
+            garden.seeds.len()
+        ));
+    }
+    let mut cur = "seed";
+    // Each step uses up a map, so a longer chain has to go round in circles
+    for _ in 0..=garden.maps.len() {
+        if cur == "location" {
+            return problems;
+        }
+        let next: Vec<&str> = garden
+            .maps
+            .iter()
+            .filter(|(from, _, _)| from == cur)
+            .map(|(_, to, _)| to.as_str())
+            .collect();
+        match next[..] {
+            [to] => cur = to,
+            [] => {
+                problems.push(format!("There is no {cur}-to-... map"));
+                return problems;
+            }
+            _ => {
+                problems.push(format!("There are {} {cur}-to-... maps", next.len()));
+                return problems;
+            }
+        }
+    }
+    problems.push("The maps go round in circles".to_string());
+    problems
+}
+
 fn run_1(input: &str) -> anyhow::Result<usize> {
-    let (_, garden) = parse(input).map_err(|e| anyhow::anyhow!(e.to_string()))?;
+    let garden = parse::parse_complete(input, parse)?;
     let mut results = HashMap::new();
     let end = "location";
     let mut cur = "seed";
@@ -100,7 +171,11 @@ fn run_1(input: &str) -> anyhow::Result<usize> {
     results.insert(cur, garden.seeds.clone());
 
     loop {
-        let (_, next, maps) = garden.maps.iter().find(|(s, _, _)| s == cur).unwrap();
+        let (_, next, maps) = garden
+            .maps
+            .iter()
+            .find(|(s, _, _)| s == cur)
+            .ok_or_else(|| anyhow::anyhow!("No {cur}-to-... map"))?;
 
         let res = results[cur].iter().map(|c| next_dest(maps, *c)).collect();
 
@@ -113,80 +188,67 @@ fn run_1(input: &str) -> anyhow::Result<usize> {
         cur = next;
     }
 
-    Ok(*results[end].iter().min().unwrap())
+    results[end]
+        .iter()
+        .min()
+        .copied()
+        .ok_or_else(|| anyhow::anyhow!("No seeds"))
 }
 
 fn run_2(input: &str) -> anyhow::Result<usize> {
-    let (_, garden) = parse(input).map_err(|e| anyhow::anyhow!(e.to_string()))?;
-    let mut results = HashMap::new();
+    let garden = parse::parse_complete(input, parse)?;
     let end = "location";
     let mut cur = "seed";
 
-    let seeds: &mut Vec<usize> = results.entry(cur).or_default();
-
-    for s in garden.seeds.chunks(2) {
-        let seed_start = s[0];
-        let seed_len = s[1];
-        for i in 0..seed_len {
-            seeds.push(seed_start + i);
-        }
+    if garden.seeds.len() % 2 != 0 {
+        anyhow::bail!("Seeds must come in start and length pairs");
     }
-
-    loop {
-        let (_, next, maps) = garden.maps.iter().find(|(s, _, _)| s == cur).unwrap();
-
-        let res = results[cur]
-            .par_iter()
-            .map(|c| next_dest(maps, *c))
-            .collect();
-
-        results.insert(next, res);
-
-        if next == end {
-            break;
-        }
-        results.remove(cur);
+    let mut ranges: IntervalSet = garden
+        .seeds
+        .chunks(2)
+        .map(|s| Interval::new(s[0] as i64, (s[0] + s[1]) as i64))
+        .collect();
+
+    while cur != end {
+        let (_, next, maps) = garden
+            .maps
+            .iter()
+            .find(|(s, _, _)| s == cur)
+            .ok_or_else(|| anyhow::anyhow!("No {cur}-to-... map"))?;
+
+        ranges = offset_table(maps).map(&ranges);
         cur = next;
     }
 
-    Ok(*results[end].iter().min().unwrap())
+    ranges
+        .min()
+        .map(|v| v as usize)
+        .ok_or_else(|| anyhow::anyhow!("No seeds"))
 }
 
 #[cfg(test)]
 mod tests {
-    const INPUT: &str = "seeds: 79 14 55 13
-
-seed-to-soil map:
-50 98 2
-52 50 48
-
-soil-to-fertilizer map:
-0 15 37
-37 52 2
-39 0 15
-
-fertilizer-to-water map:
-49 53 8
-0 11 42
-42 0 7
-57 7 4
-
-water-to-light map:
-88 18 7
-18 25 70
-
-light-to-temperature map:
-45 77 23
-81 45 19
-68 64 13
-
-temperature-to-humidity map:
-0 69 1
-1 0 69
-
-humidity-to-location map:
-60 56 37
-56 93 4";
+    #[test]
+    fn day5_validate() {
+        assert!(super::check_input(&crate::examples::input(5, "example")).is_empty());
+        assert_eq!(
+            super::check_input("seeds: 1 2 3\n\nseed-to-soil map:\n1 2 3\n"),
+            [
+                "There are 3 seeds, expected pairs of start and length",
+                "There is no soil-to-... map",
+            ]
+        );
+        assert_eq!(
+            super::check_input(
+                "seeds: 1 2\n\nseed-to-soil map:\n1 2 3\n\nsoil-to-seed map:\n1 2 3"
+            ),
+            ["The maps go round in circles"]
+        );
+        assert_eq!(
+            super::check_input("seeds: 1 2\n\nseed-to-soil map:\n1 2\n"),
+            ["Line 4, column 4: expected Digit, found '\\n'"]
+        );
+    }
 
     #[test]
     fn day5_map() {
@@ -196,14 +258,4 @@ humidity-to-location map:
         assert_eq!(map.to_dest(15), None);
         assert_eq!(map.to_dest(9), None);
     }
-
-    #[test]
-    fn day5_run_1() {
-        assert_eq!(super::run_1(INPUT).unwrap(), 35);
-    }
-
-    #[test]
-    fn day5_run_2() {
-        assert_eq!(super::run_2(INPUT).unwrap(), 46);
-    }
 }
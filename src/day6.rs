@@ -1,16 +1,33 @@
 use nom::{
     bytes::complete::tag,
     character::complete::{newline, space1},
-    multi::separated_list1,
+    error::context,
 };
 
-pub fn run() -> anyhow::Result<()> {
-    let input = std::fs::read_to_string("day6.txt")?;
+use crate::common::{math, parse, validate};
 
-    println!("6:1 - {}", run_1(&input)?);
-    println!("6:2 - {}", run_2(&input)?);
+pub struct Day;
 
-    Ok(())
+impl crate::solver::Solver for Day {
+    fn day(&self) -> usize {
+        6
+    }
+
+    fn title(&self) -> &'static str {
+        "Wait For It"
+    }
+
+    fn part1(&self, input: &str) -> anyhow::Result<crate::answer::Answer> {
+        Ok(run_1(input)?.into())
+    }
+
+    fn part2(&self, input: &str) -> anyhow::Result<crate::answer::Answer> {
+        Ok(run_2(input)?.into())
+    }
+
+    fn validate(&self, input: &str) -> Option<Vec<String>> {
+        Some(check_input(input))
+    }
 }
 
 #[derive(Debug)]
@@ -30,34 +47,25 @@ struct Race {
 // Tp * ( race time - Tp) - record_dist = 0 <=>
 // -Tp^2 + race_time*Tp - record_dist = 0
 
-// Brute force worked....
-// impl Race {
-//     // Only interested in positive values
-//     fn solve_quadratic(&self) -> Vec<f32> {
-//         let a = -1.0;
-//         let b = (self.race_time as f32);
-//         let c = -(self.record_dist as f32);
-//         let mut s1 = (-b - (b * b - 4.0 * a * c).sqrt()) / (2.0 * a);
-//         let mut s2 = (-b - (b * b + 4.0 * a * c).sqrt()) / (2.0 * a);
-//         if s2 < s1 {
-//             std::mem::swap(&mut s1, &mut s2);
-//         }
-//         if s1 >= 0.0 {
-//             vec![s1, s2]
-//         } else {
-//             vec![s2]
-//         }
-//     }
-// }
+impl Race {
+    /// The button times that beat the record, which are strictly between the
+    /// zeros above.
+    fn ways_to_win(&self) -> anyhow::Result<usize> {
+        let race_time = i64::try_from(self.race_time)?;
+        let record_dist = i64::try_from(self.record_dist)?;
+        Ok(math::between_roots(-1, race_time, -record_dist)
+            .map_or(0, |r| (r.end() - r.start() + 1) as usize))
+    }
+}
 
 fn parse(i: crate::Input) -> crate::PResult<Vec<Race>> {
     let (i, _) = tag("Time:")(i)?;
     let (i, _) = space1(i)?;
-    let (i, times) = separated_list1(space1, nom::character::complete::u32)(i)?;
+    let (i, times) = context("times", parse::ints)(i)?;
     let (i, _) = newline(i)?;
     let (i, _) = tag("Distance:")(i)?;
     let (i, _) = space1(i)?;
-    let (i, distances) = separated_list1(space1, nom::character::complete::u32)(i)?;
+    let (i, distances) = context("distances", parse::ints)(i)?;
     let (i, _) = nom::combinator::opt(newline)(i)?;
 
     Ok((
@@ -65,31 +73,49 @@ fn parse(i: crate::Input) -> crate::PResult<Vec<Race>> {
         times
             .into_iter()
             .zip(distances)
-            .map(|(t, d)| Race {
-                race_time: t as usize,
-                record_dist: d as usize,
+            .map(|(race_time, record_dist)| Race {
+                race_time,
+                record_dist,
             })
             .collect(),
     ))
 }
 
-fn run_1(input: &str) -> anyhow::Result<usize> {
-    let (_, races) = parse(input).map_err(|e| anyhow::anyhow!(e.to_string()))?;
-    let mut res = Vec::new();
-
-    for race in races {
-        let races_won = (0..=race.race_time)
-            .map(|tp| tp * (race.race_time - tp))
-            .filter(|s| *s > race.record_dist)
-            .count();
-        res.push(races_won);
+/// A line of times and a line of distances, one of each per race.
+fn check_input(input: &str) -> Vec<String> {
+    let mut problems = vec![];
+    let mut counts = vec![];
+    let mut lines = input.lines();
+    for (row, label) in ["Time:", "Distance:"].into_iter().enumerate() {
+        let line = lines.next().unwrap_or_default();
+        let values = validate::parses(line, |i| {
+            let (i, _) = tag(label)(i)?;
+            let (i, _) = space1(i)?;
+            parse::ints::<usize>(i)
+        });
+        match values {
+            Ok(values) => counts.push(values.len()),
+            Err(e) => problems.push(format!("Line {}: {e}", row + 1)),
+        }
     }
+    if let [times, distances] = counts[..] {
+        if times != distances {
+            problems.push(format!("There are {times} times but {distances} distances"));
+        }
+    }
+    if lines.any(|line| !line.trim().is_empty()) {
+        problems.push("There is more than a line of times and one of distances".to_string());
+    }
+    problems
+}
 
-    Ok(res.iter().product())
+fn run_1(input: &str) -> anyhow::Result<usize> {
+    let races = parse::parse_complete(input, parse)?;
+    races.iter().map(Race::ways_to_win).product()
 }
 
 fn run_2(input: &str) -> anyhow::Result<usize> {
-    let (_, races) = parse(input).map_err(|e| anyhow::anyhow!(e.to_string()))?;
+    let races = parse::parse_complete(input, parse)?;
 
     let race = Race {
         race_time: races
@@ -106,26 +132,5 @@ fn run_2(input: &str) -> anyhow::Result<usize> {
             .parse()?,
     };
 
-    let races_won = (0..=race.race_time)
-        .map(|tp| tp * (race.race_time - tp))
-        .filter(|s| *s > race.record_dist)
-        .count();
-
-    Ok(races_won)
-}
-
-#[cfg(test)]
-mod tests {
-    const INPUT: &str = "Time:      7  15   30
-Distance:  9  40  200";
-
-    #[test]
-    fn day6_run_1() {
-        assert_eq!(super::run_1(INPUT).unwrap(), 288);
-    }
-
-    #[test]
-    fn day6_run_2() {
-        assert_eq!(super::run_2(INPUT).unwrap(), 71503);
-    }
+    race.ways_to_win()
 }
@@ -1,16 +1,33 @@
 use std::{cmp::Ordering, collections::HashMap};
 
-pub fn run() -> anyhow::Result<()> {
-    let input = std::fs::read_to_string("day7.txt")?;
+use crate::common::validate;
 
-    println!("7:1 - {}", run_1(&input)?);
-    println!("7:2 - {}", run_2(&input)?);
+pub struct Day;
 
-    Ok(())
+impl crate::solver::Solver for Day {
+    fn day(&self) -> usize {
+        7
+    }
+
+    fn title(&self) -> &'static str {
+        "Camel Cards"
+    }
+
+    fn part1(&self, input: &str) -> anyhow::Result<crate::answer::Answer> {
+        Ok(run_1(input)?.into())
+    }
+
+    fn part2(&self, input: &str) -> anyhow::Result<crate::answer::Answer> {
+        Ok(run_2(input)?.into())
+    }
+
+    fn validate(&self, input: &str) -> Option<Vec<String>> {
+        Some(check_input(input))
+    }
 }
 
 fn run_1(input: &str) -> anyhow::Result<usize> {
-    let mut hands = parse(input);
+    let mut hands = parse(input)?;
 
     hands.sort();
 
@@ -22,7 +39,7 @@ fn run_1(input: &str) -> anyhow::Result<usize> {
 }
 
 fn run_2(input: &str) -> anyhow::Result<usize> {
-    let mut hands = parse(input);
+    let mut hands = parse(input)?;
 
     const JOKER: Card = Card(11);
     // First convert jokers to Card(1), to be low worth
@@ -77,10 +94,10 @@ fn run_2(input: &str) -> anyhow::Result<usize> {
                     // already a joker
                 }
                 t => {
-                    if num_jokers != 0 {
-                        dbg! {(t, num_jokers)};
-                        unreachable!()
-                    }
+                    anyhow::bail!(
+                        "Hand {:?} of type {t:?} has {num_jokers} joker(s)",
+                        hand.cards
+                    )
                 }
             }
         }
@@ -129,7 +146,7 @@ impl Ord for Hand {
                 .zip(&other.cards)
                 .map(|(c1, c2)| c1.0.cmp(&c2.0))
                 .find(|x| *x != Ordering::Equal)
-                .unwrap()
+                .unwrap_or(Ordering::Equal)
         } else {
             cmp
         }
@@ -217,58 +234,82 @@ impl std::cmp::Ord for Type {
     }
 }
 
-fn parse_hand(i: &str) -> Hand {
+fn parse_hand(i: &str) -> anyhow::Result<Hand> {
     let mut hb = i.split(' ');
     let cards: Vec<Card> = hb
         .next()
-        .unwrap()
+        .unwrap_or_default()
         .chars()
-        .map(|c| match c {
-            '2' => Card(2),
-            '3' => Card(3),
-            '4' => Card(4),
-            '5' => Card(5),
-            '6' => Card(6),
-            '7' => Card(7),
-            '8' => Card(8),
-            '9' => Card(9),
-            'T' => Card(10),
-            'J' => Card(11),
-            'Q' => Card(12),
-            'K' => Card(13),
-            'A' => Card(14),
-            _ => unreachable!(),
+        .enumerate()
+        .map(|(col, c)| {
+            Ok(match c {
+                '2' => Card(2),
+                '3' => Card(3),
+                '4' => Card(4),
+                '5' => Card(5),
+                '6' => Card(6),
+                '7' => Card(7),
+                '8' => Card(8),
+                '9' => Card(9),
+                'T' => Card(10),
+                'J' => Card(11),
+                'Q' => Card(12),
+                'K' => Card(13),
+                'A' => Card(14),
+                _ => anyhow::bail!("Invalid card '{c}' at column {}", col + 1),
+            })
         })
-        .collect();
+        .collect::<anyhow::Result<_>>()?;
+    if cards.len() != 5 {
+        anyhow::bail!("Expected 5 cards, got {}", cards.len());
+    }
 
-    let bid = hb.next().unwrap().parse().unwrap();
+    let bid = hb
+        .next()
+        .ok_or_else(|| anyhow::anyhow!("Missing bid"))?
+        .parse()
+        .map_err(|e| anyhow::anyhow!("Invalid bid: {e}"))?;
     let t = Hand::calculate_type(&cards);
 
-    Hand { cards, bid, t }
+    Ok(Hand { cards, bid, t })
+}
+
+/// Each line is a hand of five cards and its bid.
+fn check_input(input: &str) -> Vec<String> {
+    validate::lines(input, |line| match parse_hand(line) {
+        Ok(_) => vec![],
+        Err(e) => vec![e.to_string()],
+    })
 }
 
-fn parse(i: &str) -> Vec<Hand> {
-    i.lines().map(parse_hand).collect()
+fn parse(i: &str) -> anyhow::Result<Vec<Hand>> {
+    i.lines()
+        .enumerate()
+        .map(|(row, line)| parse_hand(line).map_err(|e| anyhow::anyhow!("Line {}: {e}", row + 1)))
+        .collect()
 }
 
 #[cfg(test)]
 mod tests {
-    const INPUT: &str = "32T3K 765
-T55J5 684
-KK677 28
-KTJJT 220
-QQQJA 483";
-
     #[test]
-    fn day7_run_1() {
-        assert_eq!(super::run_1(INPUT).unwrap(), 6440);
+    fn day7_validate() {
+        assert!(super::check_input(&crate::examples::input(7, "example")).is_empty());
+        assert_eq!(
+            super::check_input("32T3K 765\nT55X5 684\nQQQJ\nKK677 x"),
+            [
+                "Line 2: Invalid card 'X' at column 4",
+                "Line 3: Expected 5 cards, got 4",
+                "Line 4: Invalid bid: invalid digit found in string",
+            ]
+        );
     }
 
     #[test]
-    fn day7_run_2() {
-        assert_eq!(super::run_2("QQQQ2 1
-JKKK2 2").unwrap(), 4);
-
-        assert_eq!(super::run_2(INPUT).unwrap(), 5905);
+    fn day7_parse_error() {
+        let e = super::run_1("32T3K 765\nT55X5 684")
+            .unwrap_err()
+            .to_string();
+        assert_eq!(e, "Line 2: Invalid card 'X' at column 4");
+        assert!(super::run_1("32T3K").is_err());
     }
 }
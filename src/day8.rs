@@ -3,87 +3,140 @@ use std::collections::HashMap;
 use nom::{
     bytes::complete::tag,
     character::complete::newline,
+    error::context,
     multi::{many1, separated_list1},
     sequence::{preceded, separated_pair, terminated},
 };
 
-pub fn run() -> anyhow::Result<()> {
-    let input = std::fs::read_to_string("day8.txt")?;
+use crate::common::{cycle, math, parse, validate};
 
-    println!("8:1 - {}", run_1(&input)?);
-    println!("8:2 - {}", run_2(&input)?);
+pub struct Day;
 
-    Ok(())
-}
+impl crate::solver::Solver for Day {
+    fn day(&self) -> usize {
+        8
+    }
 
-fn run_1(input: &str) -> anyhow::Result<usize> {
-    let (_i, document) = parse(input).map_err(|e| anyhow::anyhow!(e.to_string()))?;
+    fn title(&self) -> &'static str {
+        "Haunted Wasteland"
+    }
 
-    let mut cur = "AAA";
-    let mut iterations = 0;
-    for i in document.instructions.iter().cycle() {
-        if cur == "ZZZ" {
-            break;
-        }
-        iterations += 1;
-        let possibles = document.network.get(cur).unwrap();
-        cur = match i {
-            Instruction::Left => &possibles.0,
-            Instruction::Right => &possibles.1,
-        }
+    fn part1(&self, input: &str) -> anyhow::Result<crate::answer::Answer> {
+        Ok(run_1(input)?.into())
+    }
+
+    fn part2(&self, input: &str) -> anyhow::Result<crate::answer::Answer> {
+        Ok(run_2(input)?.into())
+    }
+
+    fn validate(&self, input: &str) -> Option<Vec<String>> {
+        Some(check_input(input))
     }
-    Ok(iterations)
 }
 
-fn run_2(input: &str) -> anyhow::Result<usize> {
-    let (_i, document) = parse(input).map_err(|e| anyhow::anyhow!(e.to_string()))?;
-
-    let mut cur: Vec<String> = document
-        .network
-        .keys()
-        .filter_map(|n| {
-            if n.ends_with('A') {
-                Some(n.to_string())
-            } else {
-                None
-            }
-        })
-        .collect();
+/// Instructions, a blank line and the network, where every node is defined
+/// once and leads to defined nodes, and part 1 starts from AAA.
+fn check_input(input: &str) -> Vec<String> {
+    let mut problems = vec![];
+    let mut lines = input.lines();
+    let instructions = lines.next().unwrap_or_default();
+    if instructions.is_empty() {
+        problems.push("Line 1: no instructions".to_string());
+    }
+    for c in instructions.chars().filter(|c| !"LR".contains(*c)) {
+        problems.push(format!("Line 1: unexpected instruction {c:?}"));
+    }
+    if !lines.next().is_some_and(str::is_empty) {
+        problems.push("Line 2: expected a blank line".to_string());
+    }
 
-    let mut res = Vec::with_capacity(cur.len());
-    for c in cur.iter_mut() {
-        let mut iterations = 0;
-        for i in document.instructions.iter().cycle() {
-            if c.ends_with('Z') {
-                break;
+    let mut network = HashMap::new();
+    for (row, line) in lines.enumerate() {
+        match validate::parses(line, parse_network) {
+            Ok((node, next)) => {
+                if network.insert(node.clone(), next).is_some() {
+                    problems.push(format!("Line {}: node {node} is defined again", row + 3));
+                }
             }
-            iterations += 1;
-            let possibles = document.network.get(c).unwrap();
-            match i {
-                Instruction::Left => *c = possibles.0.clone(),
-                Instruction::Right => *c = possibles.1.clone(),
-            };
+            Err(e) => problems.push(format!("Line {}: {e}", row + 3)),
         }
-        res.push(iterations);
     }
-
-    Ok(lcm(&res))
+    let mut unknown: Vec<&String> = network
+        .values()
+        .flat_map(|(left, right)| [left, right])
+        .filter(|n| !network.contains_key(*n))
+        .collect();
+    unknown.sort();
+    unknown.dedup();
+    for n in unknown {
+        problems.push(format!("Node {n} is used but not defined"));
+    }
+    if !network.contains_key("AAA") {
+        problems.push("There is no node AAA".to_string());
+    }
+    problems
 }
 
-pub fn lcm(nums: &[usize]) -> usize {
-    if nums.len() == 1 {
-        return nums[0];
+fn run_1(input: &str) -> anyhow::Result<usize> {
+    let document = parse::parse_complete(input, parse)?;
+    document.check()?;
+    if !document.network.contains_key("AAA") {
+        anyhow::bail!("Unknown node 'AAA'");
     }
-    let a = nums[0];
-    let b = lcm(&nums[1..]);
-    a * b / gcd_of_two_numbers(a, b)
+
+    // Past the end of the first cycle the walk only goes round in circles
+    let start = ("AAA", 0);
+    let cycle = cycle::floyd(start, |s| document.step(s));
+    let steps = document
+        .walk(start)
+        .take(cycle.start + cycle.len)
+        .position(|(node, _)| node == "ZZZ");
+    steps.ok_or_else(|| anyhow::anyhow!("ZZZ can't be reached from AAA"))
 }
 
-fn gcd_of_two_numbers(a: usize, b: usize) -> usize {
-    if b == 0 {
-        return a;
+fn run_2(input: &str) -> anyhow::Result<usize> {
+    let document = parse::parse_complete(input, parse)?;
+    document.check()?;
+
+    // Each ghost is at an exit at the times `first + k * period`
+    let mut exits = vec![];
+    for node in document.network.keys().filter(|n| n.ends_with('A')) {
+        let start = (node.as_str(), 0);
+        let cycle = cycle::brent(start, |s| document.step(s));
+        let end = cycle.start + cycle.len;
+        let times: Vec<usize> = document
+            .walk(start)
+            .take(end)
+            .enumerate()
+            .filter(|(_, (n, _))| n.ends_with('Z'))
+            .map(|(i, _)| i)
+            .collect();
+
+        let period = match times[..] {
+            [a, b, ..] => b - a,
+            _ => cycle.len,
+        };
+        match times.first() {
+            Some(&first)
+                if first >= cycle.start
+                    && cycle.len % period == 0
+                    && times.iter().copied().eq((first..end).step_by(period)) =>
+            {
+                exits.push((first, period))
+            }
+            _ => anyhow::bail!("The ghost from {node} doesn't reach exits at a fixed interval"),
+        }
     }
-    gcd_of_two_numbers(b, a % b)
+
+    if exits.is_empty() {
+        anyhow::bail!("No start nodes ending in 'A'");
+    }
+    let congruences: Vec<_> = exits.iter().map(|(first, p)| (first % p, *p)).collect();
+    let (x, period) = math::crt(&congruences)
+        .ok_or_else(|| anyhow::anyhow!("The ghosts are never all at exits at once"))?;
+    // Before its first exit a ghost doesn't follow its pattern yet
+    let earliest = exits.iter().map(|(first, _)| *first).max().unwrap_or(0);
+    Ok(x + earliest.saturating_sub(x).div_ceil(period) * period)
 }
 
 #[derive(Debug)]
@@ -98,6 +151,37 @@ struct Document {
     network: HashMap<String, (String, String)>,
 }
 
+/// A node and the index of the next instruction.
+type State<'a> = (&'a str, usize);
+
+impl Document {
+    /// Fails if a node leads to one that isn't in the network.
+    fn check(&self) -> anyhow::Result<()> {
+        match self
+            .network
+            .values()
+            .flat_map(|(left, right)| [left, right])
+            .find(|n| !self.network.contains_key(*n))
+        {
+            Some(n) => anyhow::bail!("Unknown node '{n}'"),
+            None => Ok(()),
+        }
+    }
+
+    fn step<'a>(&'a self, (node, pos): &State<'a>) -> State<'a> {
+        let (left, right) = &self.network[*node];
+        let next = match self.instructions[*pos] {
+            Instruction::Left => left,
+            Instruction::Right => right,
+        };
+        (next, (pos + 1) % self.instructions.len())
+    }
+
+    fn walk<'a>(&'a self, start: State<'a>) -> impl Iterator<Item = State<'a>> + 'a {
+        std::iter::successors(Some(start), |s| Some(self.step(s)))
+    }
+}
+
 fn parse_network(i: crate::Input) -> crate::PResult<(String, (String, String))> {
     let parse_str = |i| {
         nom::combinator::map(nom::character::complete::alphanumeric1, |c: &str| {
@@ -121,16 +205,18 @@ fn parse_network(i: crate::Input) -> crate::PResult<(String, (String, String))>
 fn parse(i: crate::Input) -> crate::PResult<Document> {
     let left = nom::combinator::map(tag("L"), |_| Instruction::Left);
     let right = nom::combinator::map(tag("R"), |_| Instruction::Right);
-    let (i, instructions) = many1(nom::branch::alt((left, right)))(i)?;
+    let (i, instructions) = context("instructions", many1(nom::branch::alt((left, right))))(i)?;
 
-    let (i, _) = newline(i)?;
-    let (i, _) = newline(i)?;
+    let (i, _) = parse::blank_line(i)?;
 
-    let (i, network) = nom::combinator::map(separated_list1(newline, parse_network), |v| {
-        v.iter()
-            .map(|(a, (b, c))| (a.to_string(), (b.to_string(), c.to_string())))
-            .collect::<HashMap<_, _>>()
-    })(i)?;
+    let (i, network) = nom::combinator::map(
+        separated_list1(newline, context("node", parse_network)),
+        |v| {
+            v.iter()
+                .map(|(a, (b, c))| (a.to_string(), (b.to_string(), c.to_string())))
+                .collect::<HashMap<_, _>>()
+        },
+    )(i)?;
 
     Ok((
         i,
@@ -143,41 +229,54 @@ fn parse(i: crate::Input) -> crate::PResult<Document> {
 
 #[cfg(test)]
 mod tests {
-    const INPUT_1: &str = "RL
-
-AAA = (BBB, CCC)
-BBB = (DDD, EEE)
-CCC = (ZZZ, GGG)
-DDD = (DDD, DDD)
-EEE = (EEE, EEE)
-GGG = (GGG, GGG)
-ZZZ = (ZZZ, ZZZ)";
-
-    const INPUT_2: &str = "LLR
-
-AAA = (BBB, BBB)
-BBB = (AAA, ZZZ)
-ZZZ = (ZZZ, ZZZ)";
-
     #[test]
-    fn day8_run_1() {
-        assert_eq!(super::run_1(INPUT_1).unwrap(), 2);
-        assert_eq!(super::run_1(INPUT_2).unwrap(), 6);
+    fn day8_validate() {
+        assert!(super::check_input(&crate::examples::input(8, "llr")).is_empty());
+        assert_eq!(
+            super::check_input("LXR\n\nAAA = (BBB, BBB)\nAAA = (CCC, AAA)\nBBB = BBB"),
+            [
+                "Line 1: unexpected instruction 'X'",
+                "Line 4: node AAA is defined again",
+                "Line 5: column 7: expected Tag, found 'B'",
+                "Node CCC is used but not defined",
+            ]
+        );
+        assert_eq!(
+            super::check_input("L\nAAA = (AAA, AAA)"),
+            ["Line 2: expected a blank line", "There is no node AAA"]
+        );
     }
 
-    const INPUT_3: &str = "LR
+    #[test]
+    fn day8_offset_exits() {
+        // Exits after 1, 3, 5, ... and 2, 5, 8, ... steps
+        let input = "L
 
-11A = (11B, XXX)
-11B = (XXX, 11Z)
-11Z = (11B, XXX)
-22A = (22B, XXX)
-22B = (22C, 22C)
-22C = (22Z, 22Z)
-22Z = (22B, 22B)
-XXX = (XXX, XXX)";
+11A = (11Z, 11Z)
+11Z = (11B, 11B)
+11B = (11Z, 11Z)
+22A = (22B, 22B)
+22B = (22Z, 22Z)
+22Z = (22C, 22C)
+22C = (22D, 22D)
+22D = (22Z, 22Z)";
+        assert_eq!(super::run_2(input).unwrap(), 5);
+    }
 
     #[test]
-    fn day8_run_2() {
-        assert_eq!(super::run_2(INPUT_3).unwrap(), 6);
+    fn day8_no_exit() {
+        let e = super::run_1("L\n\nAAA = (BBB, ZZZ)\nBBB = (AAA, ZZZ)\nZZZ = (ZZZ, ZZZ)");
+        assert_eq!(e.unwrap_err().to_string(), "ZZZ can't be reached from AAA");
+        let e = super::run_1("L\n\nAAA = (BBB, BBB)");
+        assert_eq!(e.unwrap_err().to_string(), "Unknown node 'BBB'");
+
+        // Exits after 1, 2, 4, 5, 7, ... steps
+        let e = super::run_2(
+            "L\n\n11A = (11Z, 11Z)\n11Z = (12Z, 12Z)\n12Z = (11B, 11B)\n11B = (11Z, 11Z)",
+        );
+        assert_eq!(
+            e.unwrap_err().to_string(),
+            "The ghost from 11A doesn't reach exits at a fixed interval"
+        );
     }
 }
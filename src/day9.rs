@@ -1,65 +1,71 @@
-pub fn run() -> anyhow::Result<()> {
-    let input = std::fs::read_to_string("day9.txt")?;
+use crate::common::{math, validate};
 
-    println!("9:1 - {}", run_1(&input)?);
-    println!("9:2 - {}", run_2(&input)?);
+pub struct Day;
 
-    Ok(())
-}
+impl crate::solver::Solver for Day {
+    fn day(&self) -> usize {
+        9
+    }
 
-fn calc_next(i: &[isize]) -> Vec<isize> {
-    i.windows(2).map(|a| a[1] - a[0]).collect()
-}
+    fn title(&self) -> &'static str {
+        "Mirage Maintenance"
+    }
 
-fn find_bottom(i: &[isize]) -> isize {
-    if i.iter().all(|v| *v == 0) {
-        return 0;
+    fn part1(&self, input: &str) -> anyhow::Result<crate::answer::Answer> {
+        Ok(run_1(input)?.into())
     }
 
-    let n = calc_next(i);
+    fn part2(&self, input: &str) -> anyhow::Result<crate::answer::Answer> {
+        Ok(run_2(input)?.into())
+    }
 
-    let last = find_bottom(&n);
-    return last + i.last().unwrap();
+    fn validate(&self, input: &str) -> Option<Vec<String>> {
+        Some(check_input(input))
+    }
 }
 
-fn run_1(input: &str) -> anyhow::Result<isize> {
-    let histories: Vec<Vec<_>> = input
-        .lines()
-        .map(|line| {
-            line.split(' ')
-                .map(|c| c.parse::<isize>().unwrap())
-                .collect()
-        })
-        .collect();
-    Ok(histories.iter().map(|v| find_bottom(v)).sum())
+/// Each line is a history of integers separated by single spaces.
+fn check_input(input: &str) -> Vec<String> {
+    validate::lines(input, |line| {
+        line.split(' ')
+            .filter(|c| c.parse::<i64>().is_err())
+            .map(|c| format!("invalid value {c:?}"))
+            .collect()
+    })
 }
 
-fn run_2(input: &str) -> anyhow::Result<isize> {
-    let histories: Vec<Vec<_>> = input
+fn parse(input: &str) -> anyhow::Result<Vec<Vec<i64>>> {
+    input
         .lines()
-        .map(|line| {
+        .enumerate()
+        .map(|(row, line)| {
             line.split(' ')
-                .map(|c| c.parse::<isize>().unwrap())
-                .rev()
+                .map(|c| {
+                    c.parse::<i64>()
+                        .map_err(|e| anyhow::anyhow!("Line {}: invalid value '{c}': {e}", row + 1))
+                })
                 .collect()
         })
-        .collect();
-    Ok(histories.iter().map(|v| find_bottom(v)).sum())
+        .collect()
 }
 
-#[cfg(test)]
-mod tests {
-    const INPUT: &str = "0 3 6 9 12 15
-1 3 6 10 15 21
-10 13 16 21 30 45";
+/// The sum of every history's value at `x`, where the given values are at 0,
+/// 1, ...
+fn extrapolate_all(input: &str, x: impl Fn(&[i64]) -> i64) -> anyhow::Result<i64> {
+    parse(input)?
+        .iter()
+        .enumerate()
+        .map(|(row, h)| {
+            math::extrapolate(h, x(h))
+                .ok_or_else(|| anyhow::anyhow!("Line {}: extrapolation overflows", row + 1))
+        })
+        .sum()
+}
 
-    #[test]
-    fn day9_run_1() {
-        assert_eq!(super::run_1(INPUT).unwrap(), 114);
-    }
+fn run_1(input: &str) -> anyhow::Result<i64> {
+    extrapolate_all(input, |h| h.len() as i64)
+}
 
-    #[test]
-    fn day9_run_2() {
-        assert_eq!(super::run_2(INPUT).unwrap(), 2);
-    }
+fn run_2(input: &str) -> anyhow::Result<i64> {
+    extrapolate_all(input, |_| -1)
 }
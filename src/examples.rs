@@ -0,0 +1,99 @@
+//! Checks the solvers against the example inputs in `examples/dayNN/`.
+//! build.rs generates a test per example and part that calls [`check`], and
+//! the days' own tests take the inputs from [`input`].
+
+mod spec;
+
+use std::path::Path;
+
+use crate::solver::{self, Solver};
+
+/// The solver for `day` with the fields in `settings` changed.
+fn configure(day: usize, settings: toml::Table) -> anyhow::Result<Box<dyn Solver>> {
+    let settings = toml::Value::Table(settings);
+    Ok(match day {
+        11 => Box::new(settings.try_into::<crate::day11::Day>()?),
+        21 => Box::new(settings.try_into::<crate::day21::Day>()?),
+        24 => Box::new(settings.try_into::<crate::day24::Day>()?),
+        _ => anyhow::bail!("Day {day} has no settings"),
+    })
+}
+
+/// Reads the example at `path`, relative to the crate root, into its
+/// header and input.
+fn read(path: &str) -> anyhow::Result<(toml::Table, String)> {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(path);
+    let text = crate::input::normalize(std::fs::read_to_string(&path)?);
+    let sidecar = std::fs::read_to_string(path.with_extension("toml")).ok();
+    let (header, input) = spec::split(&text, sidecar.as_deref()).map_err(anyhow::Error::msg)?;
+    Ok((header, input.to_string()))
+}
+
+/// The input of the example `name` of `day`, for tests that need more than
+/// the answers.
+pub fn input(day: usize, name: &str) -> String {
+    let path = format!("examples/day{day:02}/{name}.txt");
+    match read(&path) {
+        Ok((_, input)) => input,
+        Err(e) => panic!("{path}: {e:#}"),
+    }
+}
+
+/// Runs `part` of `day` on the example at `path`, relative to the crate
+/// root, and compares the answer, or the error, with the expected one.
+fn run(day: usize, part: u8, path: &str) -> anyhow::Result<()> {
+    let (mut header, input) = read(path)?;
+
+    let mut answers = [1, 2].map(|p| header.remove(&spec::answer_key(p)));
+    let expected = match answers[part as usize - 1].take() {
+        Some(toml::Value::String(s)) => Ok(s),
+        Some(toml::Value::Table(t)) => match t.get("error") {
+            Some(toml::Value::String(e)) if t.len() == 1 => Err(e.clone()),
+            _ => anyhow::bail!("An expected error has to be {{ error = \"...\" }}"),
+        },
+        Some(v) => Ok(v.to_string()),
+        None => anyhow::bail!("missing expected answer"),
+    };
+
+    let configured;
+    let solver: &dyn Solver = match header.remove("solver") {
+        Some(toml::Value::Table(settings)) => {
+            configured = configure(day, settings)?;
+            &*configured
+        }
+        Some(_) => anyhow::bail!("[solver] has to be a table"),
+        None => solver::get(day).ok_or_else(|| anyhow::anyhow!("No such day"))?,
+    };
+    if let Some(key) = header.keys().next() {
+        anyhow::bail!("Unknown key '{key}'");
+    }
+
+    let answer = match part {
+        1 => solver.part1(&input),
+        _ => solver.part2(&input),
+    };
+    match (answer, expected) {
+        (Ok(answer), Ok(expected)) => anyhow::ensure!(
+            answer.to_string() == expected,
+            "expected {expected}, got {answer}"
+        ),
+        (Err(e), Err(expected)) => anyhow::ensure!(
+            e.to_string() == expected,
+            "expected the error {expected:?}, got {:?}",
+            e.to_string()
+        ),
+        (Ok(answer), Err(expected)) => {
+            anyhow::bail!("expected the error {expected:?}, got {answer}")
+        }
+        (Err(e), Ok(_)) => return Err(e),
+    }
+    Ok(())
+}
+
+fn check(day: usize, part: u8, path: &str) {
+    if let Err(e) = run(day, part, path) {
+        panic!("{path}, part {part}: {e:#}");
+    }
+}
+
+include!(concat!(env!("OUT_DIR"), "/examples.rs"));
@@ -0,0 +1,80 @@
+//! The format of the example files in `examples/dayNN/`. build.rs shares
+//! this file, so it can only use std and toml.
+//!
+//! An example is a puzzle input with its expected answers in TOML, either as
+//! front matter between `---` lines or in a sidecar file with the same name
+//! and a `.toml` extension:
+//!
+//! ```text
+//! ---
+//! part1 = 374
+//! part2 = 1030
+//!
+//! [solver]
+//! expansion = 10
+//! ---
+//! ...#......
+//! ```
+//!
+//! A part without an answer is reported as missing. A part that has to fail
+//! instead expects its error message, as `part1 = { error = "..." }`.
+//! `[solver]` sets the fields of the days that have some.
+
+/// Splits an example into its header and its input. `sidecar` is the
+/// contents of the sidecar file, if there is one.
+pub fn split<'a>(text: &'a str, sidecar: Option<&str>) -> Result<(toml::Table, &'a str), String> {
+    let (front_matter, input) = match text.strip_prefix("---\n") {
+        Some(rest) => {
+            let end = if rest.starts_with("---\n") {
+                0
+            } else {
+                rest.find("\n---\n")
+                    .ok_or("The front matter has no closing ---")?
+                    + 1
+            };
+            (Some(&rest[..end]), &rest[end + 4..])
+        }
+        None => (None, text),
+    };
+
+    let header = match (front_matter, sidecar) {
+        (Some(_), Some(_)) => return Err("Answers in both front matter and a sidecar".into()),
+        (Some(header), None) | (None, Some(header)) => header,
+        (None, None) => "",
+    };
+    let header = header.parse().map_err(|e: toml::de::Error| e.to_string())?;
+    Ok((header, input))
+}
+
+/// The header key for the expected answer to `part`.
+pub fn answer_key(part: u8) -> String {
+    format!("part{part}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{answer_key, split};
+
+    #[test]
+    fn spec_split() {
+        let (header, input) = split("---\npart1 = 4\n---\n.S.\n...\n", None).unwrap();
+        assert_eq!(input, ".S.\n...\n");
+        assert_eq!(header[&answer_key(1)].as_integer(), Some(4));
+        assert!(!header.contains_key(&answer_key(2)));
+
+        let (header, input) = split("---\n---\nabc", None).unwrap();
+        assert!(header.is_empty());
+        assert_eq!(input, "abc");
+
+        let (header, input) = split("abc\n", Some("part2 = \"x\"")).unwrap();
+        assert_eq!(header["part2"].as_str(), Some("x"));
+        assert_eq!(input, "abc\n");
+
+        let (header, _) = split("---\n[solver]\nsteps = 6\n---\n", None).unwrap();
+        assert_eq!(header["solver"]["steps"].as_integer(), Some(6));
+
+        assert!(split("---\npart1 = 4\nabc", None).is_err());
+        assert!(split("---\npart1 = 4\n---\nabc", Some("part2 = 1")).is_err());
+        assert!(split("---\npart1 =\n---\nabc", None).is_err());
+    }
+}
@@ -0,0 +1,279 @@
+use std::{
+    ffi::OsString,
+    io::Read,
+    path::{Path, PathBuf},
+};
+
+/// Environment variable naming the input directory when `--input-dir` isn't
+/// given.
+pub const INPUT_DIR_ENV: &str = "AOC_INPUT_DIR";
+
+/// Environment variable holding the adventofcode.com session cookie used to
+/// download missing inputs.
+pub const SESSION_ENV: &str = "AOC_SESSION";
+
+/// Environment variable naming a directory of dayNN.txt files to copy missing
+/// inputs from when there's no session, e.g. on machines without network.
+pub const MIRROR_ENV: &str = "AOC_INPUT_MIRROR";
+
+/// Picks the input directory: the flag, then the environment, then
+/// `inputs/<year>`.
+pub fn resolve_dir(flag: Option<&Path>, env: Option<OsString>, year: usize) -> PathBuf {
+    match (flag, env) {
+        (Some(dir), _) => dir.to_path_buf(),
+        (None, Some(dir)) if !dir.is_empty() => PathBuf::from(dir),
+        _ => Path::new("inputs").join(year.to_string()),
+    }
+}
+
+pub fn dir(flag: Option<&Path>, year: usize) -> PathBuf {
+    resolve_dir(flag, std::env::var_os(INPUT_DIR_ENV), year)
+}
+
+pub fn path(dir: &Path, day: usize) -> PathBuf {
+    dir.join(format!("day{day:02}.txt"))
+}
+
+/// Drops a byte order mark and turns CRLF, and lone CR, line endings into
+/// plain newlines, so that the solvers only ever see one form of input.
+pub fn normalize(input: String) -> String {
+    let input = match input.strip_prefix('\u{feff}') {
+        Some(rest) => rest.to_string(),
+        None => input,
+    };
+    if input.contains('\r') {
+        input.replace("\r\n", "\n").replace('\r', "\n")
+    } else {
+        input
+    }
+}
+
+/// Reads a file, or stdin if the path is "-", and normalizes it.
+pub fn read(path: &Path) -> anyhow::Result<String> {
+    if path == Path::new("-") {
+        let mut input = String::new();
+        std::io::stdin().read_to_string(&mut input)?;
+        return Ok(normalize(input));
+    }
+    std::fs::read_to_string(path)
+        .map(normalize)
+        .map_err(|e| anyhow::anyhow!("Can't read input {}: {e}", path.display()))
+}
+
+/// Somewhere to get an input from when it isn't in the cache.
+pub trait Fetcher {
+    fn fetch(&self, year: usize, day: usize) -> anyhow::Result<String>;
+}
+
+/// Downloads inputs from adventofcode.com with a session cookie.
+pub struct HttpFetcher {
+    base_url: String,
+    session: String,
+}
+
+impl HttpFetcher {
+    pub fn new(session: &str) -> Self {
+        Self {
+            base_url: "https://adventofcode.com".to_string(),
+            session: session.trim().to_string(),
+        }
+    }
+}
+
+impl Fetcher for HttpFetcher {
+    fn fetch(&self, year: usize, day: usize) -> anyhow::Result<String> {
+        let url = format!("{}/{year}/day/{day}/input", self.base_url);
+        let res = ureq::get(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .set(
+                "User-Agent",
+                "github.com/fredrik-jansson-se/advent_of_code2023",
+            )
+            .call()
+            .map_err(|e| anyhow::anyhow!("Can't download {url}: {e}"))?;
+        Ok(res.into_string()?)
+    }
+}
+
+/// Serves inputs from a directory of dayNN.txt files, for tests and offline
+/// use.
+pub struct FileFetcher {
+    dir: PathBuf,
+}
+
+impl FileFetcher {
+    pub fn new(dir: &Path) -> Self {
+        Self {
+            dir: dir.to_path_buf(),
+        }
+    }
+}
+
+impl Fetcher for FileFetcher {
+    fn fetch(&self, _year: usize, day: usize) -> anyhow::Result<String> {
+        read(&path(&self.dir, day))
+    }
+}
+
+/// Reads inputs from the cache directory, filling in missing ones from the
+/// fetcher if there is one.
+pub struct Provider {
+    cache_dir: PathBuf,
+    year: usize,
+    fetcher: Option<Box<dyn Fetcher>>,
+}
+
+impl Provider {
+    pub fn new(cache_dir: &Path, year: usize, fetcher: Option<Box<dyn Fetcher>>) -> Self {
+        Self {
+            cache_dir: cache_dir.to_path_buf(),
+            year,
+            fetcher,
+        }
+    }
+
+    /// A provider that downloads missing inputs if `AOC_SESSION` is set, or
+    /// copies them from `AOC_INPUT_MIRROR`.
+    pub fn from_env(cache_dir: &Path, year: usize) -> Self {
+        let session = std::env::var(SESSION_ENV)
+            .ok()
+            .filter(|s| !s.trim().is_empty());
+        let mirror = std::env::var_os(MIRROR_ENV).filter(|s| !s.is_empty());
+        let fetcher: Option<Box<dyn Fetcher>> = match (session, mirror) {
+            (Some(session), _) => Some(Box::new(HttpFetcher::new(&session))),
+            (None, Some(mirror)) => Some(Box::new(FileFetcher::new(Path::new(&mirror)))),
+            (None, None) => None,
+        };
+        Self::new(cache_dir, year, fetcher)
+    }
+
+    pub fn get(&self, day: usize) -> anyhow::Result<String> {
+        let path = path(&self.cache_dir, day);
+        if path.exists() {
+            return read(&path);
+        }
+        let Some(fetcher) = &self.fetcher else {
+            anyhow::bail!(
+                "No input at {} (set {SESSION_ENV} to download it)",
+                path.display()
+            );
+        };
+
+        let input = normalize(fetcher.fetch(self.year, day)?);
+        std::fs::create_dir_all(&self.cache_dir)?;
+        std::fs::write(&path, &input)
+            .map_err(|e| anyhow::anyhow!("Can't write {}: {e}", path.display()))?;
+        Ok(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+
+    use super::{FileFetcher, Provider};
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aoc2023-{}-{name}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn input_resolve_dir() {
+        assert_eq!(
+            super::resolve_dir(Some(Path::new("a")), Some("b".into()), 2023),
+            PathBuf::from("a")
+        );
+        assert_eq!(
+            super::resolve_dir(None, Some("b".into()), 2023),
+            PathBuf::from("b")
+        );
+        assert_eq!(
+            super::resolve_dir(None, Some("".into()), 2023),
+            PathBuf::from("inputs/2023")
+        );
+        assert_eq!(
+            super::resolve_dir(None, None, 2023),
+            PathBuf::from("inputs/2023")
+        );
+    }
+
+    #[test]
+    fn input_path() {
+        assert_eq!(
+            super::path(Path::new("inputs/2023"), 5),
+            PathBuf::from("inputs/2023/day05.txt")
+        );
+        assert_eq!(
+            super::path(Path::new("x"), 22),
+            PathBuf::from("x/day22.txt")
+        );
+    }
+
+    #[test]
+    fn input_normalize() {
+        let clean = "1abc2\npqr3stu8vwx\n";
+        assert_eq!(super::normalize(clean.to_string()), clean);
+        assert_eq!(
+            super::normalize("\u{feff}1abc2\r\npqr3stu8vwx\r\n".to_string()),
+            clean
+        );
+        assert_eq!(super::normalize("a\r\nb\r".to_string()), "a\nb\n");
+        // Only a leading mark is one
+        assert_eq!(super::normalize("a\u{feff}".to_string()), "a\u{feff}");
+    }
+
+    #[test]
+    fn input_read_bom_crlf() {
+        // Days that don't parse with nom get the cleaned up input too
+        let dir = temp_dir("bom-crlf");
+        for (day, answer) in [(11, 374), (15, 1320)] {
+            let input = crate::examples::input(day, "example");
+            let path = dir.join(format!("day{day:02}.txt"));
+            std::fs::write(&path, format!("\u{feff}{}", input.replace('\n', "\r\n"))).unwrap();
+
+            let input = super::read(&path).unwrap();
+            let solver = crate::solver::get(day).unwrap();
+            assert_eq!(solver.part1(&input).unwrap(), answer.into());
+        }
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn input_read_missing() {
+        let e = super::read(Path::new("no/such/day99.txt")).unwrap_err();
+        assert!(e.to_string().contains("no/such/day99.txt"));
+    }
+
+    #[test]
+    fn input_provider_cache() {
+        let remote = temp_dir("remote");
+        let cache = temp_dir("cache").join("2023");
+        std::fs::write(remote.join("day03.txt"), "remote 3").unwrap();
+
+        let provider = Provider::new(&cache, 2023, Some(Box::new(FileFetcher::new(&remote))));
+        assert_eq!(provider.get(3).unwrap(), "remote 3");
+        assert_eq!(
+            std::fs::read_to_string(cache.join("day03.txt")).unwrap(),
+            "remote 3"
+        );
+
+        // Cached inputs win over the fetcher
+        std::fs::write(remote.join("day03.txt"), "changed").unwrap();
+        assert_eq!(provider.get(3).unwrap(), "remote 3");
+
+        // The fetcher's error is passed on and nothing is cached
+        assert!(provider.get(4).is_err());
+        assert!(!cache.join("day04.txt").exists());
+
+        let offline = Provider::new(&cache, 2023, None);
+        assert_eq!(offline.get(3).unwrap(), "remote 3");
+        let e = offline.get(4).unwrap_err().to_string();
+        assert!(e.contains("day04.txt"), "{e}");
+
+        std::fs::remove_dir_all(remote).unwrap();
+        std::fs::remove_dir_all(cache.parent().unwrap()).unwrap();
+    }
+}
@@ -1,8 +1,29 @@
-use std::env;
+use clap::Parser;
+use std::time::Duration;
 
 pub type Input<'a> = &'a str;
 pub type PResult<'a, T> = nom::IResult<Input<'a>, T, nom::error::VerboseError<Input<'a>>>;
 
+/// Whether `AOC_DEBUG` is set.
+pub fn debug_enabled() -> bool {
+    static ENABLED: std::sync::OnceLock<bool> = std::sync::OnceLock::new();
+    *ENABLED.get_or_init(|| std::env::var_os("AOC_DEBUG").is_some())
+}
+
+/// Diagnostic output from the solvers. Goes to stderr, and only with
+/// `AOC_DEBUG` set, so that stdout only carries answers.
+macro_rules! debug {
+    ($($arg:tt)*) => {
+        if $crate::debug_enabled() {
+            eprintln!($($arg)*);
+        }
+    };
+}
+
+mod answer;
+mod answers;
+mod bench;
+mod cli;
 mod common;
 mod day1;
 mod day10;
@@ -29,39 +50,224 @@ mod day6;
 mod day7;
 mod day8;
 mod day9;
+#[cfg(test)]
+mod examples;
+mod input;
+mod runner;
+mod solver;
+
+fn run(
+    solver: &dyn solver::Solver,
+    args: &cli::RunArgs,
+    inputs: &input::Provider,
+) -> anyhow::Result<()> {
+    let input = match &args.input {
+        Some(path) => input::read(path)?,
+        None => inputs.get(solver.day())?,
+    };
+
+    if args.format == cli::Format::Json {
+        let results: Vec<_> = runner::quiet_panics(|| {
+            args.select
+                .parts()
+                .into_iter()
+                .map(|part| runner::run_part(solver, part, &input))
+                .collect()
+        });
+        return runner::print_json(&results);
+    }
+
+    println!("--- Day {}: {} ---", solver.day(), solver.title());
+    if args.select.runs_part(1) {
+        println!("{}:1 - {}", solver.day(), solver.part1(&input)?);
+    }
+    if args.select.runs_part(2) {
+        println!("{}:2 - {}", solver.day(), solver.part2(&input)?);
+    }
+
+    Ok(())
+}
+
+fn bench(args: &cli::BenchArgs, inputs: &input::Provider) -> anyhow::Result<()> {
+    let baseline = match &args.baseline {
+        Some(path) => bench::load(path)?,
+        None => Vec::new(),
+    };
+
+    let mut timings = Vec::new();
+    runner::quiet_panics(|| -> anyhow::Result<()> {
+        for solver in args.select.solvers()? {
+            let input = match inputs.get(solver.day()) {
+                Ok(input) => input,
+                Err(e) => {
+                    eprintln!("{}: {e:#}", solver.day());
+                    continue;
+                }
+            };
+            for part in args.select.parts() {
+                match bench::measure(solver, part, &input, args.warmup, args.iterations) {
+                    Ok(t) => timings.push(t),
+                    Err(status) => eprintln!(
+                        "{}:{} - {}: {}",
+                        solver.day(),
+                        part,
+                        status.label(),
+                        status.detail().unwrap_or("skipped")
+                    ),
+                }
+            }
+        }
+        Ok(())
+    })?;
+
+    bench::print_table(&timings, &baseline, args.threshold);
+
+    if let Some(path) = &args.save {
+        bench::save(path, &timings)?;
+    }
+
+    let regressions = bench::regressions(&timings, &baseline, args.threshold);
+    if !regressions.is_empty() {
+        anyhow::bail!("{} part(s) regressed", regressions.len());
+    }
+    Ok(())
+}
+
+fn verify(args: &cli::VerifyArgs, inputs: &input::Provider) -> anyhow::Result<()> {
+    let answers = answers::Answers::load(&args.answers)?;
+    let solvers = match args.day {
+        Some(day) => vec![solver::get(day as usize).ok_or(anyhow::anyhow!("No such day"))?],
+        None => solver::all().to_vec(),
+    };
+
+    let mut failures = 0;
+    runner::quiet_panics(|| {
+        for solver in solvers {
+            let input = inputs.get(solver.day());
+            for part in [1, 2] {
+                let outcome = match &input {
+                    Ok(input) => answers::verify(solver, part, input, &answers),
+                    Err(_) if answers.get(solver.day(), part).is_none() => {
+                        answers::Outcome::Unknown
+                    }
+                    Err(e) => answers::Outcome::Failed(runner::Status::Error(format!("{e:#}"))),
+                };
+                let msg = match outcome {
+                    answers::Outcome::Pass => "ok".to_string(),
+                    answers::Outcome::Unknown => "no answer".to_string(),
+                    answers::Outcome::Mismatch { expected, actual } => {
+                        failures += 1;
+                        format!("MISMATCH: expected {expected}, got {actual}")
+                    }
+                    answers::Outcome::Failed(status) => {
+                        failures += 1;
+                        format!(
+                            "{}: {}",
+                            status.label(),
+                            status.detail().unwrap_or("no answer produced")
+                        )
+                    }
+                };
+                println!("{}:{} - {msg}", solver.day(), part);
+            }
+        }
+    });
+
+    if failures > 0 {
+        anyhow::bail!("{failures} part(s) failed verification");
+    }
+    Ok(())
+}
+
+fn check_input(args: &cli::CheckInputArgs, inputs: &input::Provider) -> anyhow::Result<()> {
+    let solvers = match args.day {
+        Some(day) => vec![solver::get(day as usize).ok_or(anyhow::anyhow!("No such day"))?],
+        None => solver::all().to_vec(),
+    };
+
+    let mut failures = 0;
+    for solver in solvers {
+        let input = match &args.input {
+            Some(path) => input::read(path),
+            None => inputs.get(solver.day()),
+        };
+        let problems = match input {
+            Ok(input) => solver.validate(&input),
+            Err(e) => {
+                println!("{} - no input: {e:#}", solver.day());
+                continue;
+            }
+        };
+        let Some(problems) = problems else {
+            println!("{} - no checks", solver.day());
+            continue;
+        };
+        if problems.is_empty() {
+            println!("{} - ok", solver.day());
+            continue;
+        }
+        failures += 1;
+        println!("{} - {} problem(s):", solver.day(), problems.len());
+        for problem in problems {
+            println!("    {problem}");
+        }
+    }
+
+    if failures > 0 {
+        anyhow::bail!("{failures} input(s) failed the checks");
+    }
+    Ok(())
+}
 
 fn main() -> anyhow::Result<()> {
-    let mut a = env::args();
-    a.next();
-
-    let day = a.next().and_then(|s| s.parse::<usize>().ok()).unwrap_or(0);
-
-    match day {
-        1 => day1::run(),
-        2 => day2::run(),
-        3 => day3::run(),
-        4 => day4::run(),
-        5 => day5::run(),
-        6 => day6::run(),
-        7 => day7::run(),
-        8 => day8::run(),
-        9 => day9::run(),
-        10 => day10::run(),
-        11 => day11::run(),
-        12 => day12::run(),
-        13 => day13::run(),
-        14 => day14::run(),
-        15 => day15::run(),
-        16 => day16::run(),
-        17 => day17::run(),
-        18 => day18::run(),
-        19 => day19::run(),
-        20 => day20::run(),
-        21 => day21::run(),
-        22 => day22::run(),
-        23 => day23::run(),
-        24 => day24::run(),
-        25 => day25::run(),
-        _ => Err(anyhow::anyhow!("No such day")),
+    let cli = cli::Cli::parse();
+    if cli.year != solver::YEAR {
+        anyhow::bail!("No solutions for year {}", cli.year);
+    }
+    let input_dir = input::dir(cli.input_dir.as_deref(), cli.year);
+    let inputs = input::Provider::from_env(&input_dir, cli.year);
+
+    match cli.command {
+        cli::Command::Run(args) if args.select.all => {
+            let parts = args.select.parts();
+            let timeout = args.timeout.map(Duration::from_secs);
+            let mut failed = None;
+            if args.format == cli::Format::Text {
+                runner::print_header();
+            }
+            let results = runner::quiet_panics(|| {
+                runner::run_all(solver::all(), &parts, &inputs, timeout, |r| {
+                    match args.format {
+                        cli::Format::Text => runner::print_row(r),
+                        cli::Format::Json => {
+                            if let Err(e) = runner::print_json_row(r) {
+                                failed.get_or_insert(e);
+                            }
+                        }
+                    }
+                })
+            });
+            if let Some(e) = failed {
+                return Err(e);
+            }
+            if args.format == cli::Format::Text {
+                runner::print_failures(&results);
+            }
+        }
+        cli::Command::Run(args) => {
+            for solver in args.select.solvers()? {
+                run(solver, &args, &inputs)?;
+            }
+        }
+        cli::Command::Bench(args) => bench(&args, &inputs)?,
+        cli::Command::Verify(args) => verify(&args, &inputs)?,
+        cli::Command::CheckInput(args) => check_input(&args, &inputs)?,
+        cli::Command::List => {
+            for solver in solver::all() {
+                println!("{:2} {}", solver.day(), solver.title());
+            }
+        }
     }
+
+    Ok(())
 }
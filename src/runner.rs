@@ -0,0 +1,340 @@
+use std::{
+    panic::{self, AssertUnwindSafe},
+    sync::{mpsc, Arc},
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    input,
+    solver::{NotImplemented, Solver},
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Status {
+    Ok,
+    Todo,
+    Error(String),
+    Panic(String),
+    /// Still running when the time limit was up
+    Timeout,
+}
+
+impl Status {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Status::Ok => "ok",
+            Status::Todo => "todo",
+            Status::Error(_) => "error",
+            Status::Panic(_) => "panic",
+            Status::Timeout => "timeout",
+        }
+    }
+
+    pub fn detail(&self) -> Option<&str> {
+        match self {
+            Status::Error(e) | Status::Panic(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct PartResult {
+    pub day: usize,
+    pub part: u8,
+    pub answer: Option<String>,
+    pub status: Status,
+    pub elapsed: Duration,
+}
+
+#[derive(serde::Serialize)]
+struct JsonResult<'a> {
+    day: usize,
+    part: u8,
+    answer: Option<&'a str>,
+    /// Seconds
+    duration: f64,
+    error: Option<String>,
+}
+
+impl PartResult {
+    fn to_json(&self) -> JsonResult<'_> {
+        let error = match &self.status {
+            Status::Ok => None,
+            Status::Todo => Some("not implemented".to_string()),
+            Status::Error(e) => Some(e.clone()),
+            Status::Panic(e) => Some(format!("panic: {e}")),
+            Status::Timeout => Some(format!("timed out after {:.2?}", self.elapsed)),
+        };
+        JsonResult {
+            day: self.day,
+            part: self.part,
+            answer: self.answer.as_deref(),
+            duration: self.elapsed.as_secs_f64(),
+            error,
+        }
+    }
+}
+
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+/// Runs a single part, turning errors and panics into a [`Status`].
+pub fn run_part(solver: &dyn Solver, part: u8, input: &str) -> PartResult {
+    let start = Instant::now();
+    let res = panic::catch_unwind(AssertUnwindSafe(|| match part {
+        1 => solver.part1(input),
+        _ => solver.part2(input),
+    }));
+    let elapsed = start.elapsed();
+
+    let (answer, status) = match res {
+        Ok(Ok(answer)) => (Some(answer.to_string()), Status::Ok),
+        Ok(Err(e)) if e.is::<NotImplemented>() => (None, Status::Todo),
+        Ok(Err(e)) => (None, Status::Error(format!("{e:#}"))),
+        Err(payload) => {
+            let msg = panic_message(payload.as_ref());
+            if msg.starts_with("not yet implemented") {
+                (None, Status::Todo)
+            } else {
+                (None, Status::Panic(msg))
+            }
+        }
+    };
+
+    PartResult {
+        day: solver.day(),
+        part,
+        answer,
+        status,
+        elapsed,
+    }
+}
+
+/// Runs a single part on a thread of its own and gives up waiting for it
+/// after `timeout`. The thread can't be stopped, so a part that times out
+/// keeps running in the background until the process exits.
+pub fn run_part_timeout(
+    solver: &'static dyn Solver,
+    part: u8,
+    input: &Arc<str>,
+    timeout: Duration,
+) -> PartResult {
+    let (tx, rx) = mpsc::channel();
+    let input = Arc::clone(input);
+    // The solvers recurse as deep as they do on the main thread
+    let spawned = thread::Builder::new()
+        .stack_size(8 << 20)
+        .spawn(move || tx.send(run_part(solver, part, &input)));
+
+    let status = match spawned {
+        Err(e) => Status::Error(format!("Can't start a thread: {e}")),
+        Ok(_) => match rx.recv_timeout(timeout) {
+            Ok(result) => return result,
+            Err(mpsc::RecvTimeoutError::Timeout) => Status::Timeout,
+            Err(mpsc::RecvTimeoutError::Disconnected) => {
+                Status::Panic("the part's thread stopped without a result".to_string())
+            }
+        },
+    };
+    PartResult {
+        day: solver.day(),
+        part,
+        answer: None,
+        status,
+        elapsed: timeout,
+    }
+}
+
+/// Runs `f` with the panic hook silenced, since the default hook would print
+/// every panic caught by [`run_part`] to stderr.
+pub fn quiet_panics<T>(f: impl FnOnce() -> T) -> T {
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let res = f();
+    panic::set_hook(hook);
+    res
+}
+
+/// Runs the selected parts of every solver, without letting one failing day
+/// stop the others. `report` gets each result as soon as its part is done,
+/// and parts still running after `timeout` are given up on.
+pub fn run_all(
+    solvers: &[&'static dyn Solver],
+    parts: &[u8],
+    inputs: &input::Provider,
+    timeout: Option<Duration>,
+    mut report: impl FnMut(&PartResult),
+) -> Vec<PartResult> {
+    let mut results = Vec::new();
+    for solver in solvers {
+        let input: Result<Arc<str>, _> = inputs.get(solver.day()).map(Arc::from);
+        for part in parts {
+            let result = match (&input, timeout) {
+                (Ok(input), Some(timeout)) => run_part_timeout(*solver, *part, input, timeout),
+                (Ok(input), None) => run_part(*solver, *part, input),
+                (Err(e), _) => PartResult {
+                    day: solver.day(),
+                    part: *part,
+                    answer: None,
+                    status: Status::Error(format!("{e:#}")),
+                    elapsed: Duration::ZERO,
+                },
+            };
+            report(&result);
+            results.push(result);
+        }
+    }
+    results
+}
+
+/// Answers are padded to this width, wide enough for this year's, since
+/// the rows are printed before the longest answer is known.
+const ANSWER_WIDTH: usize = 16;
+
+pub fn print_header() {
+    println!(
+        "{:>3}  {:>4}  {:<ANSWER_WIDTH$}  {:<7}  {:>10}",
+        "Day", "Part", "Answer", "Status", "Time"
+    );
+}
+
+pub fn print_row(r: &PartResult) {
+    println!(
+        "{:>3}  {:>4}  {:<ANSWER_WIDTH$}  {:<7}  {:>10}",
+        r.day,
+        r.part,
+        r.answer.as_deref().unwrap_or("-"),
+        r.status.label(),
+        format!("{:.2?}", r.elapsed),
+    );
+}
+
+/// Lists what went wrong with the failed parts, after the table.
+pub fn print_failures(results: &[PartResult]) {
+    let failures: Vec<_> = results
+        .iter()
+        .filter_map(|r| r.status.detail().map(|d| (r, d)))
+        .collect();
+    if !failures.is_empty() {
+        println!();
+        for (r, detail) in failures {
+            println!("{}:{} - {}: {}", r.day, r.part, r.status.label(), detail);
+        }
+    }
+}
+
+/// Prints one JSON object per line.
+pub fn print_json(results: &[PartResult]) -> anyhow::Result<()> {
+    for r in results {
+        print_json_row(r)?;
+    }
+    Ok(())
+}
+
+pub fn print_json_row(r: &PartResult) -> anyhow::Result<()> {
+    println!("{}", serde_json::to_string(&r.to_json())?);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Status;
+
+    struct Fake;
+
+    impl crate::solver::Solver for Fake {
+        fn day(&self) -> usize {
+            99
+        }
+
+        fn title(&self) -> &'static str {
+            "Fake"
+        }
+
+        fn part1(&self, input: &str) -> anyhow::Result<crate::answer::Answer> {
+            if input.is_empty() {
+                anyhow::bail!("empty input");
+            }
+            Ok(input.len().into())
+        }
+
+        fn part2(&self, input: &str) -> anyhow::Result<crate::answer::Answer> {
+            if input.is_empty() {
+                return Err(crate::solver::NotImplemented.into());
+            }
+            if input == "todo" {
+                todo!()
+            }
+            if input == "slow" {
+                std::thread::sleep(std::time::Duration::from_secs(1));
+                return Ok(0.into());
+            }
+            panic!("bad input {input}")
+        }
+    }
+
+    #[test]
+    fn runner_run_part() {
+        let r = super::run_part(&Fake, 1, "abc");
+        assert_eq!(r.day, 99);
+        assert_eq!(r.answer.as_deref(), Some("3"));
+        assert_eq!(r.status, Status::Ok);
+
+        let r = super::run_part(&Fake, 1, "");
+        assert_eq!(r.answer, None);
+        assert_eq!(r.status, Status::Error("empty input".to_string()));
+
+        let r = super::run_part(&Fake, 2, "");
+        assert_eq!(r.status, Status::Todo);
+
+        let r = super::run_part(&Fake, 2, "todo");
+        assert_eq!(r.status, Status::Todo);
+
+        let r = super::run_part(&Fake, 2, "x");
+        assert_eq!(r.status, Status::Panic("bad input x".to_string()));
+    }
+
+    #[test]
+    fn runner_timeout() {
+        use std::{sync::Arc, time::Duration};
+
+        let r = super::run_part_timeout(&Fake, 1, &Arc::from("abc"), Duration::from_secs(5));
+        assert_eq!(r.answer.as_deref(), Some("3"));
+        assert_eq!(r.status, Status::Ok);
+
+        let r = super::run_part_timeout(&Fake, 2, &Arc::from("x"), Duration::from_secs(5));
+        assert_eq!(r.status, Status::Panic("bad input x".to_string()));
+
+        let r = super::run_part_timeout(&Fake, 2, &Arc::from("slow"), Duration::from_millis(50));
+        assert_eq!(r.answer, None);
+        assert_eq!(r.status, Status::Timeout);
+        assert_eq!(r.elapsed, Duration::from_millis(50));
+        let v = serde_json::to_value(r.to_json()).unwrap();
+        assert_eq!(v["error"], "timed out after 50.00ms");
+    }
+
+    #[test]
+    fn runner_json() {
+        let r = super::run_part(&Fake, 1, "abc");
+        let v = serde_json::to_value(r.to_json()).unwrap();
+        assert_eq!(v["day"], 99);
+        assert_eq!(v["part"], 1);
+        assert_eq!(v["answer"], "3");
+        assert!(v["duration"].is_f64());
+        assert!(v["error"].is_null());
+
+        let r = super::run_part(&Fake, 1, "");
+        let v = serde_json::to_value(r.to_json()).unwrap();
+        assert!(v["answer"].is_null());
+        assert_eq!(v["error"], "empty input");
+    }
+}
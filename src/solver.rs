@@ -0,0 +1,83 @@
+use crate::answer::Answer;
+
+pub const YEAR: usize = 2023;
+
+/// Returned by parts that haven't been solved yet.
+#[derive(Debug)]
+pub struct NotImplemented;
+
+impl std::fmt::Display for NotImplemented {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "not implemented")
+    }
+}
+
+impl std::error::Error for NotImplemented {}
+
+/// A single day of the calendar.
+pub trait Solver: Sync {
+    fn day(&self) -> usize;
+    fn title(&self) -> &'static str;
+    fn part1(&self, input: &str) -> anyhow::Result<Answer>;
+    fn part2(&self, input: &str) -> anyhow::Result<Answer>;
+
+    /// What's wrong with `input` as far as the solver's assumptions about it
+    /// go, one problem per entry, or `None` if the day doesn't check.
+    fn validate(&self, _input: &str) -> Option<Vec<String>> {
+        None
+    }
+}
+
+static SOLVERS: [&dyn Solver; 25] = [
+    &crate::day1::Day,
+    &crate::day2::Day,
+    &crate::day3::Day,
+    &crate::day4::Day,
+    &crate::day5::Day,
+    &crate::day6::Day,
+    &crate::day7::Day,
+    &crate::day8::Day,
+    &crate::day9::Day,
+    &crate::day10::Day,
+    &crate::day11::Day::DEFAULT,
+    &crate::day12::Day,
+    &crate::day13::Day,
+    &crate::day14::Day,
+    &crate::day15::Day,
+    &crate::day16::Day,
+    &crate::day17::Day,
+    &crate::day18::Day,
+    &crate::day19::Day,
+    &crate::day20::Day,
+    &crate::day21::Day::DEFAULT,
+    &crate::day22::Day,
+    &crate::day23::Day,
+    &crate::day24::Day::DEFAULT,
+    &crate::day25::Day,
+];
+
+/// All registered solvers, ordered by day.
+pub fn all() -> &'static [&'static dyn Solver] {
+    &SOLVERS
+}
+
+pub fn get(day: usize) -> Option<&'static dyn Solver> {
+    all().iter().copied().find(|s| s.day() == day)
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn solver_registry() {
+        let days: Vec<usize> = super::all().iter().map(|s| s.day()).collect();
+        assert_eq!(days, (1..=25).collect::<Vec<_>>());
+        assert!(super::all().iter().all(|s| !s.title().is_empty()));
+        assert_eq!(super::get(7).unwrap().title(), "Camel Cards");
+        assert!(super::get(0).is_none());
+        assert!(super::all().iter().all(|s| s.validate("").is_some()));
+        assert_eq!(
+            super::get(10).unwrap().validate("S-7\n|.|\nL-J"),
+            Some(vec![])
+        );
+    }
+}